@@ -1,10 +1,11 @@
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{AccountId, PromiseOrValue, env, near_bindgen};
-use crate::Contract;
-use near_sdk::json_types::U128;
-use crate::nft::{TokenId, SaleId};
-
-// const FT_TOKEN: AccountId = AccountId::new_unchecked("mfight-ft.testnet".to_string());
+use crate::*;
+use near_sdk::json_types::{U128, Base58CryptoHash};
+use crate::nft::{TokenId, SaleId, storage_used_since};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::offer::OfferTarget;
+use crate::nft::craft::RecipeId;
 
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -15,6 +16,20 @@ pub struct FtTransferArgs {
   pub sale_id: Option<SaleId>,
   pub receiver_id: Option<AccountId>,
   pub mint_amount: Option<u64>,
+  // merkle proof of the receiver for allowlisted sale phases
+  pub proof: Option<Vec<Base58CryptoHash>>,
+  // auction bid
+  pub auction_token_id: Option<TokenId>,
+  // market purchase, the token goes to `receiver_id` or to the sender
+  pub market_token_id: Option<TokenId>,
+  // offer escrow
+  pub offer: Option<OfferTarget>,
+  pub offer_expires_at: Option<u64>,
+  // crafting fee, the tokens of the sender are burned for the recipe output
+  pub craft_recipe_id: Option<RecipeId>,
+  pub craft_token_ids: Option<Vec<TokenId>>,
+  // vault buyout, the token goes to `receiver_id` or to the sender
+  pub vault_token_id: Option<TokenId>,
 }
 
 /// callbacks from FT Contracts
@@ -31,12 +46,86 @@ impl FungibleTokenReceiver for Contract {
       sale_id,
       receiver_id,
       mint_amount,
+      proof,
+      auction_token_id,
+      market_token_id,
+      offer,
+      offer_expires_at,
+      craft_recipe_id,
+      craft_token_ids,
+      vault_token_id,
     } = near_sdk::serde_json::from_str(&msg).expect("Invalid FtTransferArgs");
     let ft_token_id = env::predecessor_account_id();
 
+    if let Some(craft_recipe_id) = craft_recipe_id {
+      self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::Craft, &ft_token_id);
+
+      let fee = self.tokens.recipe_by_id.get(&craft_recipe_id).expect("Not found recipe").fee.0;
+      let initial_storage_usage = env::storage_usage();
+
+      let craft = self.tokens.internal_craft(&sender_id, &craft_recipe_id, craft_token_ids.unwrap_or_default(), Some(ft_token_id), amount.0);
+      self.tokens.internal_storage_use_registered(&sender_id, storage_used_since(initial_storage_usage) + craft.storage);
+
+      return PromiseOrValue::Value(U128::from(amount.0 - fee));
+    }
+
+    if let Some(vault_token_id) = vault_token_id {
+      self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::Vault, &ft_token_id);
+
+      let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
+      let initial_storage_usage = env::storage_usage();
+      let vault = self.tokens.internal_vault_buyout(&vault_token_id, &sender_id, &receiver_id, amount.0, Some(ft_token_id));
+
+      self.tokens.internal_storage_use_registered(&sender_id, storage_used_since(initial_storage_usage));
+
+      return PromiseOrValue::Value(U128::from(amount.0 - vault.reserve_price.0));
+    }
+    if let Some(offer) = offer {
+      self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::Offer, &ft_token_id);
+
+      // the predecessor is the FT contract, the sender is checked separately
+      self.assert_offer(&offer);
+      self.blacklist.assert_not_blocked(&sender_id);
+
+      let expires_at = offer_expires_at.expect("Offer expiration is required");
+      let initial_storage_usage = env::storage_usage();
+
+      self.tokens.internal_offer_make(&sender_id, offer, Some(ft_token_id), amount.0, expires_at);
+      self.tokens.internal_storage_use_registered(&sender_id, storage_used_since(initial_storage_usage));
+
+      return PromiseOrValue::Value(U128::from(0));
+    }
+    if let Some(market_token_id) = market_token_id {
+      self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::Market, &ft_token_id);
+
+      let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
+      // the predecessor is the FT contract, the sender is checked separately
+      self.assert_transfer(&market_token_id, &receiver_id);
+      self.blacklist.assert_not_blocked(&sender_id);
+
+      let initial_storage_usage = env::storage_usage();
+      let listing = self.tokens.internal_market_buy(&market_token_id, &sender_id, &receiver_id, amount.0, Some(ft_token_id));
+
+      self.tokens.internal_storage_use_registered(&sender_id, storage_used_since(initial_storage_usage));
+
+      return PromiseOrValue::Value(U128::from(amount.0 - listing.price.0));
+    }
+    if let Some(auction_token_id) = auction_token_id {
+      self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::Auction, &ft_token_id);
+
+      // the predecessor is the FT contract, the sender is checked separately
+      self.assert_transfer(&auction_token_id, &sender_id);
+      self.blacklist.assert_not_blocked(&sender_id);
+
+      let initial_storage_usage = env::storage_usage();
+      self.tokens.internal_auction_bid(&auction_token_id, &sender_id, amount.0, Some(ft_token_id));
+
+      self.tokens.internal_storage_use_registered(&sender_id, storage_used_since(initial_storage_usage));
+
+      return PromiseOrValue::Value(U128::from(0));
+    }
     if let Some(token_id) = token_id {
-      // unimplemented!();
-      assert_eq!(ft_token_id, AccountId::new_unchecked("mfight-xp.testnet".to_string()), "Unavailable ft");
+      self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::Upgrade, &ft_token_id);
       assert!(amount.0 > 0, "Amount must be greater than 0");
 
       let owner_id = self.tokens.owner_by_id.get(&token_id).expect("Not found token");
@@ -44,26 +133,34 @@ impl FungibleTokenReceiver for Contract {
 
       let next_rarity = self.tokens.assert_next_rarity(&token_id);
       let token_type = self.tokens.token_type_by_id.as_mut().unwrap().get(&token_id).expect("Not found token");
-      let price = self.tokens.internal_upgrade_price(&token_type,&next_rarity);
+      let price = self.tokens.internal_upgrade_price(&ft_token_id, &token_type, &next_rarity);
 
-      assert_eq!(price, amount, "Invalid attached price");
+      assert!(amount.0 >= price.0, "Invalid attached price");
 
-      self.tokens.internal_upgrade_token_unguarded(&owner_id, &token_id, &amount, &next_rarity);
+      self.tokens.internal_upgrade_token_unguarded(&owner_id, &token_id, &price, &next_rarity);
 
-      return PromiseOrValue::Value(U128::from(0));
+      return PromiseOrValue::Value(U128::from(amount.0 - price.0));
     }
     if let Some(sale_id) = sale_id {
       if let Some(receiver_id) = receiver_id {
         if let Some(mint_amount) = mint_amount {
-          let sale = self.tokens.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+          let sale_price = self.tokens.internal_sale_price(&sale_id);
           let ft_token = self.tokens.sale_by_ft_token.as_ref().unwrap().get(&sale_id).expect("Mint only with NEAR");
+          self.tokens.ft_whitelist.assert_whitelisted(&FtPurpose::SaleMint, &ft_token_id);
 
-          assert_eq!(sale.price.0 * (mint_amount as u128), amount.0, "Invalid attached price");
+          assert!(amount.0 >= sale_price * (mint_amount as u128), "Invalid attached price");
           assert_eq!(ft_token_id, ft_token, "Unavailable ft");
 
-          self.tokens.internal_random_mint(&receiver_id, &sale_id, &mint_amount);
+          // mint what is left, the unused amount is returned to the sender
+          let mint_amount = self.tokens.internal_sale_fill_amount(&sale_id, &mint_amount);
+          let price = sale_price * (mint_amount as u128);
+          let initial_storage_usage = env::storage_usage();
+
+          let reservation = self.tokens.internal_sale_reserve(&sender_id, &receiver_id, &sale_id, &mint_amount, sale_price, Some(ft_token_id), &proof);
+
+          self.tokens.internal_storage_use_registered(&sender_id, storage_used_since(initial_storage_usage) + reservation.storage);
 
-          return PromiseOrValue::Value(U128::from(0));
+          return PromiseOrValue::Value(U128::from(amount.0 - price));
         }
       }
     }
@@ -1,15 +1,16 @@
-use near_sdk::{AccountId, BorshStorageKey, env, near_bindgen, PanicOnDefault, Promise, PromiseOrValue, StorageUsage};
+use near_sdk::{AccountId, Balance, BorshStorageKey, env, near_bindgen, PanicOnDefault, Promise, PromiseOrValue, StorageUsage};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedSet};
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedMap, UnorderedSet};
 use near_sdk::json_types::U128;
 
 use crate::nft::*;
-use crate::nft::metadata::{TokenType, NFT_METADATA_SPEC, NonFungibleTokenMetadataProvider, TokenMetadata, TokenSubType};
+use crate::nft::metadata::{TokenType, NFT_METADATA_SPEC, NonFungibleTokenMetadataProvider, TokenMetadata, TokenSubType, ContractSourceMetadataProvider, ContractSourceMetadata, Standard};
 use std::collections::HashMap;
 use crate::blacklist::BlacklistFeature;
 use crate::pause::PauseFeature;
 use crate::nft::royalty::RoyaltyFeature;
 use crate::nft::bind_to_owner::BindToOwnerFeature;
+use crate::nft::ft_whitelist::FtWhitelistFeature;
 
 mod nft;
 mod event;
@@ -71,6 +72,70 @@ enum StorageKey {
 
   // Bind to owner extension
   BindToOwner,
+
+  // Storage management
+  StorageBalance,
+
+  // Upgradable
+  UpgradePrices,
+
+  // Ft whitelist
+  FtWhitelist,
+  UpgradePricesByFt,
+
+  // Sale treasury
+  SaleTreasury,
+
+  // Sale phases
+  SalePhases,
+
+  // Dutch auction
+  SaleDutchAuction,
+
+  // Auctions
+  Auctions,
+
+  // Market
+  Market,
+
+  // Offers
+  Offers,
+
+  // Sale reservations
+  SaleReservations,
+
+  // Not minted sale tokens, `SaleRandomTokens` keeps them until they are migrated
+  SaleRandomTokensIndexed,
+
+  // Sale templates
+  SaleTemplates,
+
+  // Sale drop tables
+  SaleDropTables,
+
+  // Packs
+  Packs,
+
+  // Crafting
+  Crafting,
+
+  // Fractionation reversal
+  FractionationReversal,
+
+  // Vaults
+  Vaults,
+
+  // Default royalties
+  RoyaltyDefaults,
+
+  // Royalty changes
+  RoyaltyChanges,
+
+  // Transfer fees
+  TransferFees,
+
+  // Payout claims
+  PayoutClaims,
 }
 
 #[near_bindgen]
@@ -107,7 +172,7 @@ impl Contract {
       Some(StorageKey::SaleByToken),
       Some(StorageKey::SaleTokens),
       Some(StorageKey::SaleLocked),
-      Some(StorageKey::SaleRandomTokens),
+      Some(StorageKey::SaleRandomTokensIndexed),
       Some(StorageKey::SaleMintCounter),
       Some(StorageKey::SalesAvailable),
       Some(StorageKey::SaleByFt),
@@ -125,6 +190,30 @@ impl Contract {
       Some(StorageKey::FractionationsCompleted),
 
      StorageKey::BindToOwner,
+
+      NonFungibleTokenPrefixes {
+        storage_balance: StorageKey::StorageBalance.try_to_vec().unwrap(),
+        upgrade_prices: StorageKey::UpgradePricesByFt.try_to_vec().unwrap(),
+        ft_whitelist: StorageKey::FtWhitelist.try_to_vec().unwrap(),
+        sale_treasury: StorageKey::SaleTreasury.try_to_vec().unwrap(),
+        sale_phases: StorageKey::SalePhases.try_to_vec().unwrap(),
+        sale_dutch: StorageKey::SaleDutchAuction.try_to_vec().unwrap(),
+        auction: StorageKey::Auctions.try_to_vec().unwrap(),
+        market: StorageKey::Market.try_to_vec().unwrap(),
+        offer: StorageKey::Offers.try_to_vec().unwrap(),
+        sale_reservation: StorageKey::SaleReservations.try_to_vec().unwrap(),
+        random_tokens_legacy: StorageKey::SaleRandomTokens.try_to_vec().unwrap(),
+        sale_template: StorageKey::SaleTemplates.try_to_vec().unwrap(),
+        sale_drop: StorageKey::SaleDropTables.try_to_vec().unwrap(),
+        pack: StorageKey::Packs.try_to_vec().unwrap(),
+        craft: StorageKey::Crafting.try_to_vec().unwrap(),
+        fractionation_reversal: StorageKey::FractionationReversal.try_to_vec().unwrap(),
+        vault: StorageKey::Vaults.try_to_vec().unwrap(),
+        royalty_defaults: StorageKey::RoyaltyDefaults.try_to_vec().unwrap(),
+        royalty_changes: StorageKey::RoyaltyChanges.try_to_vec().unwrap(),
+        transfer_fee: StorageKey::TransferFees.try_to_vec().unwrap(),
+        payout_claims: StorageKey::PayoutClaims.try_to_vec().unwrap(),
+      },
     );
 
     Self {
@@ -203,7 +292,7 @@ impl Contract {
 
     let old: Old = env::state_read().expect("Error");
 
-    let tokens = NonFungibleToken {
+    let mut tokens = NonFungibleToken {
       // owner of contract
       owner_id: old.tokens.owner_id.clone(),
       // royalty_account_id: old.tokens.royalty_account_id,
@@ -231,7 +320,8 @@ impl Contract {
       sales_available: old.tokens.sales_available,
       sale_by_id: old.tokens.sale_by_id,
       sale_date_by_id: old.tokens.sale_date_by_id,
-      sale_random_tokens: old.tokens.sale_random_tokens,
+      // not minted tokens are moved to the indexed storage by `nft_sale_migrate_tokens`
+      sale_random_tokens: Some(LookupMap::new(StorageKey::SaleRandomTokensIndexed.try_to_vec().unwrap())),
       sale_mint_counter: old.tokens.sale_mint_counter,
       sale_by_ft_token: old.tokens.sale_by_ft_token,
 
@@ -257,8 +347,132 @@ impl Contract {
       fractionation_by_id: old.tokens.fractionation_by_id,
       fractionation_ids: old.tokens.fractionation_ids,
       fractionation_completed_by_id: old.tokens.fractionation_completed_by_id,
+
+      // ===== Storage management =====
+      storage_balance_by_id: LookupMap::new(StorageKey::StorageBalance.try_to_vec().unwrap()),
+
+      // ===== Upgradable =====
+      upgrade_prices: UnorderedMap::new(StorageKey::UpgradePricesByFt.try_to_vec().unwrap()),
+
+      // ===== Ft whitelist =====
+      ft_whitelist: FtWhitelistFeature::new(StorageKey::FtWhitelist.try_to_vec().unwrap()),
+
+      // ===== Sale treasury =====
+      sale_proceeds_by_id: LookupMap::new(StorageKey::SaleTreasury.try_to_vec().unwrap()),
+      sale_beneficiaries_by_id: LookupMap::new([StorageKey::SaleTreasury.try_to_vec().unwrap(), "b".into()].concat()),
+
+      // ===== Sale phases =====
+      sale_phases_by_id: LookupMap::new(StorageKey::SalePhases.try_to_vec().unwrap()),
+      sale_phase_mint_counter: LookupMap::new([StorageKey::SalePhases.try_to_vec().unwrap(), "c".into()].concat()),
+
+      // ===== Dutch auction =====
+      sale_dutch_by_id: LookupMap::new(StorageKey::SaleDutchAuction.try_to_vec().unwrap()),
+      sale_dutch_purchases: LookupMap::new([StorageKey::SaleDutchAuction.try_to_vec().unwrap(), "p".into()].concat()),
+      sale_dutch_totals: LookupMap::new([StorageKey::SaleDutchAuction.try_to_vec().unwrap(), "t".into()].concat()),
+
+      // ===== Auctions =====
+      auction_by_token: UnorderedMap::new(StorageKey::Auctions.try_to_vec().unwrap()),
+      auctions_per_owner: LookupMap::new([StorageKey::Auctions.try_to_vec().unwrap(), "o".into()].concat()),
+
+      // ===== Market =====
+      listing_by_token: UnorderedMap::new(StorageKey::Market.try_to_vec().unwrap()),
+      listings_per_owner: LookupMap::new([StorageKey::Market.try_to_vec().unwrap(), "o".into()].concat()),
+      listings_per_collection: LookupMap::new([StorageKey::Market.try_to_vec().unwrap(), "c".into()].concat()),
+      listings_by_price: TreeMap::new([StorageKey::Market.try_to_vec().unwrap(), "p".into()].concat()),
+
+      // ===== Offers =====
+      offer_by_id: UnorderedMap::new(StorageKey::Offers.try_to_vec().unwrap()),
+      offers_per_target: LookupMap::new([StorageKey::Offers.try_to_vec().unwrap(), "t".into()].concat()),
+      offers_per_buyer: LookupMap::new([StorageKey::Offers.try_to_vec().unwrap(), "b".into()].concat()),
+      offers_next_id: 0,
+
+      // ===== Sale reservations =====
+      sale_reservation_by_id: LookupMap::new(StorageKey::SaleReservations.try_to_vec().unwrap()),
+      sale_reservations_per_account: LookupMap::new([StorageKey::SaleReservations.try_to_vec().unwrap(), "a".into()].concat()),
+      sale_reserved_by_id: LookupMap::new([StorageKey::SaleReservations.try_to_vec().unwrap(), "r".into()].concat()),
+      sale_reservations_next_id: 0,
+
+      // ===== Sale tokens migration =====
+      sale_random_tokens_legacy: LookupMap::new(StorageKey::SaleRandomTokens.try_to_vec().unwrap()),
+
+      // ===== Sale templates =====
+      sale_templates_by_id: LookupMap::new(StorageKey::SaleTemplates.try_to_vec().unwrap()),
+
+      // ===== Sale drop tables =====
+      sale_drop_table_by_id: LookupMap::new(StorageKey::SaleDropTables.try_to_vec().unwrap()),
+      sale_drop_pools: LookupMap::new([StorageKey::SaleDropTables.try_to_vec().unwrap(), "p".into()].concat()),
+      sale_drop_pity: LookupMap::new([StorageKey::SaleDropTables.try_to_vec().unwrap(), "c".into()].concat()),
+
+      // ===== Packs =====
+      pack_by_token: LookupMap::new(StorageKey::Packs.try_to_vec().unwrap()),
+
+      // ===== Crafting =====
+      recipe_by_id: UnorderedMap::new(StorageKey::Crafting.try_to_vec().unwrap()),
+      recipe_crafted_per_account: LookupMap::new([StorageKey::Crafting.try_to_vec().unwrap(), "a".into()].concat()),
+      craft_pending_by_id: LookupMap::new([StorageKey::Crafting.try_to_vec().unwrap(), "p".into()].concat()),
+      craft_next_id: 0,
+
+      // ===== Fractionation reversal =====
+      fractionation_reversal_by_id: LookupMap::new(StorageKey::FractionationReversal.try_to_vec().unwrap()),
+      fractionation_parts_by_id: LookupMap::new([StorageKey::FractionationReversal.try_to_vec().unwrap(), "p".into()].concat()),
+
+      // ===== Vaults =====
+      vault_by_token: UnorderedMap::new(StorageKey::Vaults.try_to_vec().unwrap()),
+      vault_shares: LookupMap::new([StorageKey::Vaults.try_to_vec().unwrap(), "s".into()].concat()),
+
+      // ===== Default royalties =====
+      royalty_by_collection: LookupMap::new(StorageKey::RoyaltyDefaults.try_to_vec().unwrap()),
+      royalty_by_type: LookupMap::new([StorageKey::RoyaltyDefaults.try_to_vec().unwrap(), "t".into()].concat()),
+
+      // ===== Royalty changes =====
+      royalty_proposal_by_id: LookupMap::new(StorageKey::RoyaltyChanges.try_to_vec().unwrap()),
+      royalty_history_by_id: LookupMap::new([StorageKey::RoyaltyChanges.try_to_vec().unwrap(), "h".into()].concat()),
+
+      // ===== Transfer fees =====
+      transfer_fee_by_token: LookupMap::new(StorageKey::TransferFees.try_to_vec().unwrap()),
+      transfer_fee_by_collection: LookupMap::new([StorageKey::TransferFees.try_to_vec().unwrap(), "c".into()].concat()),
+      transfer_fee_exempt: UnorderedSet::new([StorageKey::TransferFees.try_to_vec().unwrap(), "e".into()].concat()),
+
+      // ===== Payout claims =====
+      payout_claims: LookupMap::new(StorageKey::PayoutClaims.try_to_vec().unwrap()),
     };
 
+    // upgrades used to accept only this token at hardcoded prices, keep them for the deployed contract
+    let upgrade_ft_token_id = AccountId::new_unchecked("mfight-xp.testnet".to_string());
+    tokens.ft_whitelist.internal_whitelist_add(&FtPurpose::Upgrade, &upgrade_ft_token_id);
+
+    let upgrade_rarities = [
+      TokenRarity::Uncommon,
+      TokenRarity::Rare,
+      TokenRarity::Uniq,
+      TokenRarity::Epic,
+      TokenRarity::Legendary,
+      TokenRarity::Artefact,
+    ];
+    let upgrade_prices: [(TokenType, [u128; 6]); 7] = [
+      (TokenType::Armor, [2, 7, 24, 81, 273, 921]),
+      (TokenType::Weapon, [4, 12, 40, 135, 455, 1535]),
+      (TokenType::Shield, [4, 12, 40, 135, 455, 1535]),
+      (TokenType::Pet, [3, 11, 36, 121, 409, 1381]),
+      (TokenType::Jewelry, [2, 7, 24, 81, 273, 921]),
+      (TokenType::Class, [4, 14, 48, 162, 546, 1842]),
+      (TokenType::Race, [2, 8, 28, 94, 318, 1074]),
+    ];
+    for (token_type, prices) in upgrade_prices.iter() {
+      for (rarity, price) in upgrade_rarities.iter().zip(prices.iter()) {
+        tokens.internal_set_upgrade_price(&upgrade_ft_token_id, token_type, rarity, &U128::from(price * 10u128.pow(24)));
+      }
+    }
+
+    // sales already priced in a fungible token keep accepting it
+    let sale_ft_tokens: Vec<AccountId> = tokens.sale_by_id.as_ref().unwrap()
+      .keys()
+      .filter_map(|sale_id| tokens.sale_by_ft_token.as_ref().unwrap().get(sale_id))
+      .collect();
+    sale_ft_tokens.iter().for_each(|ft_token_id| {
+      tokens.ft_whitelist.internal_whitelist_add(&FtPurpose::SaleMint, ft_token_id);
+    });
+
     Self {
       tokens,
       owner_id: old.owner_id,
@@ -289,6 +503,10 @@ impl Contract {
     self.assert_caller();
     self.pause.assert_not_pause();
   }
+  pub fn assert_offer(&self, target: &OfferTarget) {
+    self.pause.assert_not_pause();
+    self.assert_caller();
+  }
 }
 
 impl_non_fungible_token_core!(Contract, tokens, assert_transfer);
@@ -301,9 +519,22 @@ impl_non_fungible_token_payout!(Contract, tokens, assert_transfer);
 impl_non_fungible_token_sales!(Contract, tokens);
 impl_non_fungible_token_fractionation!(Contract, tokens);
 impl_non_fungible_token_mint!(Contract, tokens);
+impl_non_fungible_token_deposit!(Contract, tokens);
+impl_storage_management!(Contract, tokens);
+impl_non_fungible_token_upgrade_price!(Contract, tokens);
+impl_non_fungible_token_auction!(Contract, tokens, assert_transfer);
+impl_non_fungible_token_market!(Contract, tokens, assert_transfer);
+impl_non_fungible_token_offer!(Contract, tokens, assert_transfer, assert_offer);
+impl_non_fungible_token_pack!(Contract, tokens, assert_transfer);
+impl_non_fungible_token_craft!(Contract, tokens, assert_burn);
+impl_non_fungible_token_vault!(Contract, tokens, assert_transfer);
+impl_non_fungible_token_transfer_fee!(Contract, tokens, assert_transfer);
 
 impl_bind_to_owner_feature!(Contract, tokens);
 impl_royalty_feature!(Contract, tokens, assert_owner);
+impl_royalty_defaults!(Contract, tokens);
+impl_royalty_changes!(Contract, tokens);
+impl_ft_whitelist_feature!(Contract, tokens, assert_owner);
 
 impl_pause_feature!(Contract, pause, assert_owner);
 impl_blacklist_feature!(Contract, blacklist, assert_owner);
@@ -316,3 +547,98 @@ impl NonFungibleTokenMetadataProvider for Contract {
   }
 }
 
+#[near_bindgen]
+impl ContractSourceMetadataProvider for Contract {
+  fn contract_source_metadata(&self) -> ContractSourceMetadata {
+    ContractSourceMetadata {
+      version: Some(env!("CARGO_PKG_VERSION").to_string()),
+      link: None,
+      standards: vec![
+        Standard::new("nep145", "1.0.0"),
+        Standard::new("nep171", "1.0.0"),
+        Standard::new("nep177", NFT_METADATA_SPEC.trim_start_matches("nft-")),
+        Standard::new("nep178", "1.0.0"),
+        Standard::new("nep181", "1.0.0"),
+        Standard::new("nep199", "2.0.0"),
+        Standard::new("nep297", "1.0.0"),
+        Standard::new("nep330", "1.1.0"),
+      ],
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::nft::test_utils::*;
+
+  /// Writes `contract` in the layout of the deployed contract, before the extensions were added
+  fn write_deployed_state(contract: &Contract, sale_random_tokens: &LookupMap<SaleId, Vec<TokenId>>) {
+    let tokens = &contract.tokens;
+    let state = (
+      &contract.owner_id,
+      (
+        &tokens.owner_id,
+        &tokens.extra_storage_in_bytes_per_token,
+        &tokens.owner_by_id,
+        &tokens.token_metadata_by_id,
+        &tokens.tokens_per_owner,
+        &tokens.approvals_by_id,
+        &tokens.next_approval_id_by_id,
+      ),
+      (
+        &tokens.sale_by_token,
+        &tokens.sale_tokens,
+        &tokens.sales_locked,
+        &tokens.sales_available,
+        &tokens.sale_by_id,
+        &tokens.sale_date_by_id,
+        Some(sale_random_tokens),
+        &tokens.sale_mint_counter,
+        &tokens.sale_by_ft_token,
+      ),
+      (
+        &tokens.royalty,
+        &tokens.bind_to_owner,
+        &tokens.token_rarity_by_id,
+        &tokens.token_collection_by_id,
+        &tokens.token_type_by_id,
+        &tokens.token_sub_type_by_id,
+        &tokens.fractionation_token_by_id,
+        &tokens.fractionation_by_id,
+        &tokens.fractionation_ids,
+        &tokens.fractionation_completed_by_id,
+      ),
+      &contract.metadata,
+      &contract.pause,
+      &contract.blacklist,
+    );
+
+    env::storage_write(b"STATE", &state.try_to_vec().unwrap());
+  }
+
+  #[test]
+  fn migrate_from_deployed_state() {
+    set_context(&context(&owner(), 0));
+    let mut contract = Contract::new_with_default_meta(owner());
+    mint(&mut contract.tokens, "token", Some(&account("alice")), None);
+    let mut sale_random_tokens = LookupMap::new(StorageKey::SaleRandomTokens);
+    sale_random_tokens.insert(&"sale".to_string(), &vec!["a".to_string(), "b".to_string()]);
+    write_deployed_state(&contract, &sale_random_tokens);
+
+    let contract = Contract::migrate();
+
+    assert_eq!(contract.tokens.owner_by_id.get(&"token".to_string()), Some(account("alice")));
+    assert_eq!(contract.tokens.sale_random_tokens_legacy.get(&"sale".to_string()), Some(vec!["a".to_string(), "b".to_string()]));
+    assert!(contract.tokens.sale_random_tokens.as_ref().unwrap().get(&"sale".to_string()).is_none());
+
+    let upgrade_ft_token_id: AccountId = "mfight-xp.testnet".parse().unwrap();
+    assert!(contract.tokens.ft_whitelist.internal_is_whitelisted(&FtPurpose::Upgrade, &upgrade_ft_token_id));
+    assert_eq!(contract.tokens.upgrade_prices.len(), 42);
+    assert_eq!(
+      contract.tokens.upgrade_prices.get(&(upgrade_ft_token_id, TokenType::Weapon, TokenRarity::Rare)),
+      Some(12 * NEAR)
+    );
+  }
+}
@@ -3,8 +3,7 @@
 use crate::nft::approval::NonFungibleTokenApproval;
 use crate::nft::token::TokenId;
 use crate::nft::utils::{
-  bytes_for_approved_account_id, refund_approved_account_ids,
-  refund_approved_account_ids_iter,
+  refund_approved_account_ids, refund_approved_account_ids_iter, storage_used_since,
 };
 use crate::nft::NonFungibleToken;
 use near_sdk::{assert_one_yocto, env, ext_contract, require, AccountId, Balance, Gas, Promise};
@@ -61,6 +60,8 @@ impl NonFungibleTokenApproval for NonFungibleToken {
 
     self.assert_available_approve(&token_id, &account_id);
 
+    let initial_storage_usage = env::storage_usage();
+
     let approvals_by_id = self
       .approvals_by_id
       .as_mut()
@@ -74,7 +75,7 @@ impl NonFungibleTokenApproval for NonFungibleToken {
     // update HashMap of approvals for this token
     let approved_account_ids = &mut approvals_by_id.get(&token_id).unwrap_or_default();
     let approval_id: u64 = next_approval_id_by_id.get(&token_id).unwrap_or(1u64);
-    let old_approval_id = approved_account_ids.insert(account_id.clone(), approval_id);
+    approved_account_ids.insert(account_id.clone(), approval_id);
 
     // save updated approvals HashMap to contract's LookupMap
     approvals_by_id.insert(&token_id, approved_account_ids);
@@ -85,9 +86,7 @@ impl NonFungibleTokenApproval for NonFungibleToken {
     // If this approval replaced existing for same account, no storage was used.
     // Otherwise, require that enough deposit was attached to pay for storage, and refund
     // excess.
-    // let storage_used =
-    //   if old_approval_id.is_none() { bytes_for_approved_account_id(&account_id) } else { 0 };
-    // refund_deposit(storage_used);
+    self.internal_storage_charge(&owner_id, storage_used_since(initial_storage_usage), 0);
 
     let final_msg = msg.clone();
 
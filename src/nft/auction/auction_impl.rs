@@ -0,0 +1,247 @@
+use near_sdk::{env, require, AccountId};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, refund_approved_account_ids, storage_used_since, assert_at_least_one_yocto};
+use crate::nft::auction::{NonFungibleTokenAuction, NonFungibleTokenAuctionEnumeration, Auction};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::events_171_mf::{AuctionCreate, AuctionCancel, AuctionSettle};
+
+impl NonFungibleTokenAuction for NonFungibleToken {
+  fn nft_auction_add(
+    &mut self,
+    token_id: TokenId,
+    ft_token: Option<AccountId>,
+    reserve_price: U128,
+    min_bid_increment: U128,
+    start_date: u64,
+    end_date: u64,
+    extension: u64,
+  ) -> Auction {
+    // the rest of the deposit pays for storage of unregistered owners
+    assert_at_least_one_yocto();
+    let owner_id = self.assert_token_holder(&token_id);
+    let initial_storage_usage = env::storage_usage();
+
+    self.assert_available_transfer(&token_id, &owner_id);
+    self.assert_token_not_in_auction(&token_id);
+    self.assert_token_not_listed(&token_id);
+
+    assert!(start_date < end_date, "Auction must end after start");
+    assert!(end_date > env::block_timestamp(), "Auction end must be in the future");
+    assert!(min_bid_increment.0 > 0, "Min bid increment must be greater than 0");
+
+    if let Some(ft_token) = &ft_token {
+      self.ft_whitelist.assert_whitelisted(&FtPurpose::Auction, ft_token);
+    }
+
+    let auction = Auction {
+      token_id: token_id.clone(),
+      owner_id: owner_id.clone(),
+      ft_token,
+      reserve_price,
+      min_bid_increment,
+      start_date,
+      end_date,
+      extension,
+      bid: None,
+    };
+
+    self.internal_auction_insert(&auction);
+
+    AuctionCreate {
+      auction: &auction,
+    }.emit();
+
+    self.internal_storage_charge(&owner_id, storage_used_since(initial_storage_usage), 0);
+
+    auction
+  }
+
+  fn nft_auction_cancel(&mut self, token_id: TokenId) {
+    let auction = self.auction_by_token.get(&token_id).expect("Not found auction");
+
+    assert_eq!(env::predecessor_account_id(), auction.owner_id, "Unauthorized");
+    assert!(auction.bid.is_none(), "Auction already has bids");
+
+    self.internal_auction_remove(&auction);
+
+    AuctionCancel {
+      token_id: &token_id,
+    }.emit();
+  }
+
+  fn nft_auction_bid(&mut self, token_id: TokenId) -> Auction {
+    let account_id = env::predecessor_account_id();
+
+    self.internal_auction_bid(&token_id, &account_id, env::attached_deposit(), None)
+  }
+
+  fn nft_auction_settle(&mut self, token_id: TokenId) -> Auction {
+    let auction = self.auction_by_token.get(&token_id).expect("Not found auction");
+
+    assert!(env::block_timestamp() >= auction.end_date, "Auction is not ended");
+
+    self.internal_auction_remove(&auction);
+
+    if let Some(bid) = &auction.bid {
+      self.bind_to_owner.assert_bind_to_player(&token_id);
+      self.internal_transfer_unguarded(&token_id, &auction.owner_id, &bid.account_id);
+
+      if let Some(approved_account_ids) = self.approvals_by_id.as_mut().and_then(|by_id| by_id.remove(&token_id)) {
+        refund_approved_account_ids(auction.owner_id.clone(), &approved_account_ids);
+      }
+
+      let payout = self.internal_payout(&token_id, &auction.owner_id, bid.amount.0, None);
+
+      payout
+        .iter()
+        .filter(|(_, amount)| amount.0 > 0)
+        .for_each(|(receiver_id, amount)| {
+          self.internal_payout_transfer(&auction.ft_token, receiver_id, amount.0, format!("Auction {} proceeds", token_id));
+        });
+    }
+
+    AuctionSettle {
+      token_id: &token_id,
+      owner_id: &auction.owner_id,
+      winner_id: auction.bid.as_ref().map(|bid| &bid.account_id),
+      amount: auction.bid.as_ref().map(|bid| &bid.amount),
+    }.emit();
+
+    auction
+  }
+}
+
+impl NonFungibleTokenAuctionEnumeration for NonFungibleToken {
+  fn nft_auction(&self, token_id: TokenId) -> Option<Auction> {
+    self.auction_by_token.get(&token_id)
+  }
+
+  fn nft_auctions(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.auction_by_token.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.auction_by_token
+      .values()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+
+  fn nft_auctions_for_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction> {
+    let token_set = if let Some(token_set) = self.auctions_per_owner.get(&account_id) {
+      token_set
+    } else {
+      return vec![];
+    };
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    token_set
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .map(|token_id| self.auction_by_token.get(&token_id).unwrap())
+      .collect()
+  }
+
+  fn nft_auctions_supply(&self) -> U128 {
+    U128::from(self.auction_by_token.len() as u128)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use near_sdk::{Balance, PromiseResult};
+  use crate::nft::payout::{PayoutClaims, PayoutClaimsResolver};
+  use crate::nft::test_utils::*;
+
+  fn auction(tokens: &mut NonFungibleToken, seller_id: &AccountId) -> TokenId {
+    mint(tokens, "token", Some(seller_id), None);
+
+    set_context(context(seller_id, NEAR).block_timestamp(10));
+    tokens.nft_auction_add("token".to_string(), None, U128::from(5 * NEAR), U128::from(NEAR), 100, 1_000, 50);
+
+    "token".to_string()
+  }
+
+  fn bid(tokens: &mut NonFungibleToken, token_id: &TokenId, account_id: &AccountId, amount: Balance, timestamp: u64) -> Auction {
+    set_context(context(account_id, amount).block_timestamp(timestamp));
+
+    tokens.nft_auction_bid(token_id.clone())
+  }
+
+  #[test]
+  fn outbid_refunds_previous_bidder_and_extends_the_end() {
+    let mut tokens = new_tokens();
+    let (seller, alice, bob) = (account("seller"), account("alice"), account("bob"));
+    let token_id = auction(&mut tokens, &seller);
+
+    bid(&mut tokens, &token_id, &alice, 5 * NEAR, 200);
+    assert!(transfers().is_empty());
+
+    let auction = bid(&mut tokens, &token_id, &bob, 6 * NEAR, 980);
+    assert_eq!(transfers(), vec![(alice, 5 * NEAR)]);
+    assert_eq!(auction.bid.unwrap().account_id, bob);
+    assert_eq!(auction.end_date, 1_030);
+  }
+
+  #[test]
+  #[should_panic(expected = "Bid must be at least")]
+  fn bid_below_increment_fails() {
+    let mut tokens = new_tokens();
+    let token_id = auction(&mut tokens, &account("seller"));
+
+    bid(&mut tokens, &token_id, &account("alice"), 5 * NEAR, 200);
+    bid(&mut tokens, &token_id, &account("bob"), 6 * NEAR - 1, 300);
+  }
+
+  #[test]
+  fn settle_transfers_token_and_pays_seller() {
+    let mut tokens = new_tokens();
+    let (seller, alice) = (account("seller"), account("alice"));
+    let token_id = auction(&mut tokens, &seller);
+
+    bid(&mut tokens, &token_id, &alice, 7 * NEAR, 200);
+
+    set_context(context(&account("anyone"), 0).block_timestamp(1_000));
+    tokens.nft_auction_settle(token_id.clone());
+
+    assert_eq!(tokens.owner_by_id.get(&token_id), Some(alice));
+    assert_eq!(transfers(), vec![(seller, 7 * NEAR)]);
+    assert!(tokens.nft_auction(token_id).is_none());
+  }
+
+  #[test]
+  #[should_panic(expected = "Auction is not ended")]
+  fn settle_before_end_fails() {
+    let mut tokens = new_tokens();
+    let token_id = auction(&mut tokens, &account("seller"));
+
+    set_context(context(&account("anyone"), 0).block_timestamp(999));
+    tokens.nft_auction_settle(token_id);
+  }
+
+  #[test]
+  fn failed_refund_can_be_claimed() {
+    let mut tokens = new_tokens();
+    let alice = account("alice");
+
+    set_callback_context(PromiseResult::Failed);
+    assert_eq!(tokens.nft_resolve_payout(None, alice.clone(), U128::from(5 * NEAR)).0, 0);
+    assert_eq!(tokens.nft_payout_claimable(alice.clone(), None).0, 5 * NEAR);
+
+    set_context(&context(&alice, 0));
+    assert_eq!(tokens.nft_payout_claim(None).0, 5 * NEAR);
+    assert_eq!(transfers(), vec![(alice.clone(), 5 * NEAR)]);
+    assert_eq!(tokens.nft_payout_claimable(alice, None).0, 0);
+  }
+}
@@ -0,0 +1,91 @@
+use near_sdk::{AccountId, env, Balance, BorshStorageKey};
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId};
+use crate::nft::auction::{Auction, AuctionBid};
+use crate::nft::events_171_mf::AuctionBid as AuctionBidEvent;
+
+/// Hashed values are tagged, other modules use the same variant index for keys hashed by account
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum StorageKey {
+  AuctionsPerOwnerInner { account_hash: Vec<u8> },
+}
+
+impl NonFungibleToken {
+  pub(crate) fn assert_token_not_in_auction(&self, token_id: &TokenId) {
+    if self.auction_by_token.get(token_id).is_some() {
+      env::panic_str("Token in auction");
+    }
+  }
+
+  pub(crate) fn internal_auction_insert(&mut self, auction: &Auction) {
+    let mut owner_auctions = self.auctions_per_owner.get(&auction.owner_id).unwrap_or_else(|| {
+      UnorderedSet::new(StorageKey::AuctionsPerOwnerInner {
+        account_hash: env::sha256(format!("auctions:{}", auction.owner_id).as_bytes()),
+      })
+    });
+    owner_auctions.insert(&auction.token_id);
+
+    self.auctions_per_owner.insert(&auction.owner_id, &owner_auctions);
+    self.auction_by_token.insert(&auction.token_id, auction);
+  }
+
+  pub(crate) fn internal_auction_remove(&mut self, auction: &Auction) {
+    if let Some(mut owner_auctions) = self.auctions_per_owner.get(&auction.owner_id) {
+      owner_auctions.remove(&auction.token_id);
+
+      if owner_auctions.is_empty() {
+        self.auctions_per_owner.remove(&auction.owner_id);
+      } else {
+        self.auctions_per_owner.insert(&auction.owner_id, &owner_auctions);
+      }
+    }
+
+    self.auction_by_token.remove(&auction.token_id);
+  }
+
+  /// Place a bid of `amount` in `ft_token` (`None` for NEAR) and refund the previous bid
+  pub(crate) fn internal_auction_bid(&mut self, token_id: &TokenId, account_id: &AccountId, amount: Balance, ft_token: Option<AccountId>) -> Auction {
+    let mut auction = self.auction_by_token.get(token_id).expect("Not found auction");
+    let now = env::block_timestamp();
+
+    assert_eq!(auction.ft_token, ft_token, "Invalid auction currency");
+    assert!(now >= auction.start_date, "Auction is not started");
+    assert!(now < auction.end_date, "Auction is ended");
+    assert_ne!(&auction.owner_id, account_id, "Owner cannot bid");
+
+    let min_amount = match &auction.bid {
+      Some(bid) => bid.amount.0 + auction.min_bid_increment.0,
+      None => auction.reserve_price.0,
+    };
+    assert!(amount >= min_amount, "Bid must be at least {}", min_amount);
+    self.assert_transfer_fee_price(token_id, &auction.owner_id, account_id, amount);
+
+    if let Some(bid) = &auction.bid {
+      self.internal_payout_transfer(&auction.ft_token, &bid.account_id, bid.amount.0, format!("Auction {} outbid", token_id));
+    }
+
+    // anti-sniping, a late bid keeps the auction open for `extension` more
+    if auction.end_date - now < auction.extension {
+      auction.end_date = now + auction.extension;
+    }
+
+    auction.bid = Some(AuctionBid {
+      account_id: account_id.clone(),
+      amount: U128::from(amount),
+      date: now,
+    });
+
+    self.auction_by_token.insert(token_id, &auction);
+
+    AuctionBidEvent {
+      token_id,
+      account_id,
+      amount: &U128::from(amount),
+      end_date: &auction.end_date,
+    }.emit();
+
+    auction
+  }
+}
@@ -0,0 +1,63 @@
+// Auction
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_auction {
+    ($contract: ident, $tokens: ident $(, $assert_transfer: ident)?) => {
+        use $crate::nft::auction::{NonFungibleTokenAuction, NonFungibleTokenAuctionEnumeration, Auction};
+
+        #[near_bindgen]
+        impl NonFungibleTokenAuction for $contract {
+          #[payable]
+          fn nft_auction_add(
+            &mut self,
+            token_id: TokenId,
+            ft_token: Option<AccountId>,
+            reserve_price: U128,
+            min_bid_increment: U128,
+            start_date: u64,
+            end_date: u64,
+            extension: u64,
+          ) -> Auction {
+            $(self.$assert_transfer(&token_id, &env::current_account_id());)?
+
+            self.$tokens.nft_auction_add(token_id, ft_token, reserve_price, min_bid_increment, start_date, end_date, extension)
+          }
+
+          fn nft_auction_cancel(&mut self, token_id: TokenId) {
+            self.$tokens.nft_auction_cancel(token_id)
+          }
+
+          #[payable]
+          fn nft_auction_bid(&mut self, token_id: TokenId) -> Auction {
+            $(self.$assert_transfer(&token_id, &env::predecessor_account_id());)?
+
+            self.$tokens.nft_auction_bid(token_id)
+          }
+
+          fn nft_auction_settle(&mut self, token_id: TokenId) -> Auction {
+            $(self.$assert_transfer(&token_id, &env::predecessor_account_id());)?
+
+            self.$tokens.nft_auction_settle(token_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenAuctionEnumeration for $contract {
+          fn nft_auction(&self, token_id: TokenId) -> Option<Auction> {
+            self.$tokens.nft_auction(token_id)
+          }
+
+          fn nft_auctions(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction> {
+            self.$tokens.nft_auctions(from_index, limit)
+          }
+
+          fn nft_auctions_for_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction> {
+            self.$tokens.nft_auctions_for_owner(account_id, from_index, limit)
+          }
+
+          fn nft_auctions_supply(&self) -> U128 {
+            self.$tokens.nft_auctions_supply()
+          }
+        }
+    };
+}
@@ -0,0 +1,28 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use crate::nft::TokenId;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuctionBid {
+  pub account_id: AccountId,
+  pub amount: U128,
+  pub date: u64,
+}
+
+/// `ft_token` is `None` for auctions in NEAR
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Auction {
+  pub token_id: TokenId,
+  pub owner_id: AccountId,
+  pub ft_token: Option<AccountId>,
+  pub reserve_price: U128,
+  pub min_bid_increment: U128,
+  pub start_date: u64,
+  pub end_date: u64,
+  pub extension: u64,
+  pub bid: Option<AuctionBid>,
+}
@@ -0,0 +1,43 @@
+pub use metadata::*;
+use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::TokenId;
+
+pub mod metadata;
+pub mod auction_impl;
+mod internal;
+mod macros;
+
+/// English auctions of single tokens, bids are in NEAR or in a whitelisted FT
+pub trait NonFungibleTokenAuction {
+  /// Put the token of the caller on auction, a bid in the last `extension` nanoseconds moves the end
+  fn nft_auction_add(
+    &mut self,
+    token_id: TokenId,
+    ft_token: Option<AccountId>,
+    reserve_price: U128,
+    min_bid_increment: U128,
+    start_date: u64,
+    end_date: u64,
+    extension: u64,
+  ) -> Auction;
+
+  /// Seller can cancel the auction until the first bid
+  fn nft_auction_cancel(&mut self, token_id: TokenId);
+
+  /// Bid the attached deposit, the previous highest bidder is refunded
+  fn nft_auction_bid(&mut self, token_id: TokenId) -> Auction;
+
+  /// Transfer the token to the highest bidder and pay the seller and royalties, anyone can call it after the end
+  fn nft_auction_settle(&mut self, token_id: TokenId) -> Auction;
+}
+
+pub trait NonFungibleTokenAuctionEnumeration {
+  fn nft_auction(&self, token_id: TokenId) -> Option<Auction>;
+
+  fn nft_auctions(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction>;
+
+  fn nft_auctions_for_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction>;
+
+  fn nft_auctions_supply(&self) -> U128;
+}
@@ -3,18 +3,29 @@ use crate::nft::base::NonFungibleTokenCore;
 use crate::nft::metadata::{TokenMetadata, TokenRarity, TokenCollection, TokenType, TokenSubType};
 use crate::nft::token::{Token, TokenId};
 use crate::nft::utils::{
-  hash_account_id, refund_approved_account_ids,
+  hash_account_id, refund_approved_account_ids, storage_used_since, assert_at_least_one_yocto,
 };
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, TreeMap, UnorderedSet};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
 use near_sdk::json_types::{Base64VecU8};
-use near_sdk::{assert_one_yocto, env, ext_contract, log, require, AccountId, Balance, Gas, IntoStorageKey, PromiseOrValue, PromiseResult, StorageUsage, BorshStorageKey, CryptoHash};
+use near_sdk::{env, ext_contract, log, require, AccountId, Balance, Gas, IntoStorageKey, PromiseOrValue, PromiseResult, StorageUsage, BorshStorageKey, CryptoHash};
 use std::collections::HashMap;
 use crate::{SaleId, Sale};
-use crate::nft::royalty::{Royalty, RoyaltyFeature};
+use crate::nft::sale::{SaleProceeds, SalePhase, DutchAuction, DutchAuctionTotals, SaleReservation, SaleReservationId, SaleTemplate, DropTable};
+use crate::nft::auction::Auction;
+use crate::nft::market::Listing;
+use crate::nft::offer::{Offer, OfferId, OfferTarget};
+use crate::nft::pack::Pack;
+use crate::nft::craft::{Recipe, RecipeId, PendingCraft, CraftId};
+use crate::nft::fractionation::{FractionationReversal, FractionationPart};
+use crate::nft::vault::Vault;
+use crate::nft::transfer_fee::{TransferFee, TransferFeeDeposit};
+use crate::nft::transfer_fee::internal::refund_transfer_deposit;
+use crate::nft::royalty::{Royalty, RoyaltyFeature, RoyaltyProposal, RoyaltyChange};
 use crate::nft::bind_to_owner::BindToOwnerFeature;
+use crate::nft::ft_whitelist::FtWhitelistFeature;
 
-const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(10_000_000_000_000);
 const GAS_FOR_NFT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.0);
 
 const NO_DEPOSIT: Balance = 0;
@@ -33,6 +44,7 @@ trait NFTResolver {
     receiver_id: AccountId,
     token_id: TokenId,
     approved_account_ids: Option<HashMap<AccountId, u64>>,
+    transfer_fee: Option<TransferFeeDeposit>,
   ) -> bool;
 }
 
@@ -85,7 +97,7 @@ pub struct NonFungibleToken {
   pub sales_available: Option<UnorderedSet<SaleId>>,
   pub sale_by_id: Option<HashMap<SaleId, Sale>>,
   pub sale_date_by_id: Option<HashMap<SaleId, u64>>,
-  pub sale_random_tokens: Option<LookupMap<SaleId, Vec<TokenId>>>,
+  pub sale_random_tokens: Option<LookupMap<SaleId, Vector<TokenId>>>,
   pub sale_mint_counter: Option<LookupMap<SaleId, LookupMap<AccountId, u32>>>,
   pub sale_by_ft_token: Option<LookupMap<SaleId, AccountId>>,
 
@@ -107,10 +119,126 @@ pub struct NonFungibleToken {
   pub fractionation_by_id: Option<TreeMap<TokenId, UnorderedSet<TokenId>>>,
   pub fractionation_ids: Option<UnorderedSet<TokenId>>,
   pub fractionation_completed_by_id: Option<LookupMap<TokenId, u64>>,
+
+  // required by storage management extension
+  pub storage_balance_by_id: LookupMap<AccountId, Balance>,
+
+  // required by upgradable extension
+  pub upgrade_prices: UnorderedMap<(AccountId, TokenType, TokenRarity), Balance>,
+
+  // fungible tokens accepted by ft_on_transfer
+  pub ft_whitelist: FtWhitelistFeature,
+
+  // ====== Sale treasury ======
+  pub sale_proceeds_by_id: LookupMap<SaleId, Vec<SaleProceeds>>,
+  pub sale_beneficiaries_by_id: LookupMap<SaleId, HashMap<AccountId, u32>>,
+
+  // ====== Sale phases ======
+  pub sale_phases_by_id: LookupMap<SaleId, Vec<SalePhase>>,
+  pub sale_phase_mint_counter: LookupMap<(SaleId, String, AccountId), u32>,
+
+  // ====== Dutch auction ======
+  pub sale_dutch_by_id: LookupMap<SaleId, DutchAuction>,
+  pub sale_dutch_purchases: LookupMap<(SaleId, AccountId), (u64, Balance)>,
+  pub sale_dutch_totals: LookupMap<SaleId, DutchAuctionTotals>,
+
+  // ====== Auctions ======
+  pub auction_by_token: UnorderedMap<TokenId, Auction>,
+  pub auctions_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+
+  // ====== Market ======
+  pub listing_by_token: UnorderedMap<TokenId, Listing>,
+  pub listings_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+  pub listings_per_collection: LookupMap<TokenCollection, UnorderedSet<TokenId>>,
+  pub listings_by_price: TreeMap<(Option<AccountId>, Balance, TokenId), ()>,
+
+  // ====== Offers ======
+  pub offer_by_id: UnorderedMap<OfferId, Offer>,
+  pub offers_per_target: LookupMap<OfferTarget, UnorderedSet<OfferId>>,
+  pub offers_per_buyer: LookupMap<AccountId, UnorderedSet<OfferId>>,
+  pub offers_next_id: OfferId,
+
+  // ====== Sale reservations ======
+  pub sale_reservation_by_id: LookupMap<SaleReservationId, SaleReservation>,
+  pub sale_reservations_per_account: LookupMap<AccountId, UnorderedSet<SaleReservationId>>,
+  pub sale_reserved_by_id: LookupMap<SaleId, u64>,
+  pub sale_reservations_next_id: SaleReservationId,
+
+  // ====== Sale tokens migration ======
+  // not minted tokens stored as one Vec per sale before they moved to `sale_random_tokens`
+  pub sale_random_tokens_legacy: LookupMap<SaleId, Vec<TokenId>>,
+
+  // ====== Sale templates ======
+  pub sale_templates_by_id: LookupMap<SaleId, Vec<SaleTemplate>>,
+
+  // ====== Sale drop tables ======
+  pub sale_drop_table_by_id: LookupMap<SaleId, DropTable>,
+  pub sale_drop_pools: LookupMap<SaleId, Vec<Vector<TokenId>>>,
+  pub sale_drop_pity: LookupMap<(SaleId, AccountId), Vec<u32>>,
+
+  // ====== Packs ======
+  pub pack_by_token: LookupMap<TokenId, Pack>,
+
+  // ====== Crafting ======
+  pub recipe_by_id: UnorderedMap<RecipeId, Recipe>,
+  pub recipe_crafted_per_account: LookupMap<(RecipeId, AccountId), u32>,
+  pub craft_pending_by_id: LookupMap<CraftId, PendingCraft>,
+  pub craft_next_id: CraftId,
+
+  // ====== Fractionation reversal ======
+  pub fractionation_reversal_by_id: LookupMap<TokenId, FractionationReversal>,
+  pub fractionation_parts_by_id: LookupMap<TokenId, Vec<FractionationPart>>,
+
+  // ====== Vaults ======
+  pub vault_by_token: UnorderedMap<TokenId, Vault>,
+  pub vault_shares: LookupMap<(TokenId, AccountId), Balance>,
+
+  // ====== Default royalties ======
+  pub royalty_by_collection: LookupMap<TokenCollection, Royalty>,
+  pub royalty_by_type: LookupMap<TokenType, Royalty>,
+
+  // ====== Royalty changes ======
+  pub royalty_proposal_by_id: LookupMap<TokenId, RoyaltyProposal>,
+  pub royalty_history_by_id: LookupMap<TokenId, Vector<RoyaltyChange>>,
+
+  // ====== Transfer fees ======
+  pub transfer_fee_by_token: LookupMap<TokenId, TransferFee>,
+  pub transfer_fee_by_collection: LookupMap<TokenCollection, TransferFee>,
+  pub transfer_fee_exempt: UnorderedSet<AccountId>,
+
+  // ====== Payout claims ======
+  pub payout_claims: LookupMap<(AccountId, Option<AccountId>), Balance>,
 }
 
 
+/// Storage prefixes of the collections added after the first deployment, `migrate` creates them
+/// with the same keys
+pub struct NonFungibleTokenPrefixes {
+  pub storage_balance: Vec<u8>,
+  pub upgrade_prices: Vec<u8>,
+  pub ft_whitelist: Vec<u8>,
+  pub sale_treasury: Vec<u8>,
+  pub sale_phases: Vec<u8>,
+  pub sale_dutch: Vec<u8>,
+  pub auction: Vec<u8>,
+  pub market: Vec<u8>,
+  pub offer: Vec<u8>,
+  pub sale_reservation: Vec<u8>,
+  pub random_tokens_legacy: Vec<u8>,
+  pub sale_template: Vec<u8>,
+  pub sale_drop: Vec<u8>,
+  pub pack: Vec<u8>,
+  pub craft: Vec<u8>,
+  pub fractionation_reversal: Vec<u8>,
+  pub vault: Vec<u8>,
+  pub royalty_defaults: Vec<u8>,
+  pub royalty_changes: Vec<u8>,
+  pub transfer_fee: Vec<u8>,
+  pub payout_claims: Vec<u8>,
+}
+
 impl NonFungibleToken {
+  #[allow(clippy::too_many_arguments)]
   pub fn new<Q, R, S, T, S1, S2, S3, S4, S5, S6, S7, R1, E1, E2, E3, E4, F1, F2, F3, F4, B>(
     owner_by_id_prefix: Q,
     owner_id: AccountId,
@@ -144,6 +272,8 @@ impl NonFungibleToken {
     fractionation_completed_prefix: Option<F4>,
 
     bind_to_owner_prefix: B,
+
+    prefixes: NonFungibleTokenPrefixes,
   ) -> Self
     where
       Q: IntoStorageKey,
@@ -173,7 +303,7 @@ impl NonFungibleToken {
       // F5: IntoStorageKey,
       // F6: IntoStorageKey,
 
-      B: IntoStorageKey
+      B: IntoStorageKey,
   {
     let (approvals_by_id, next_approval_id_by_id) = if let Some(prefix) = approval_prefix {
       let prefix: Vec<u8> = prefix.into_storage_key();
@@ -184,6 +314,29 @@ impl NonFungibleToken {
     } else {
       (None, None)
     };
+    let NonFungibleTokenPrefixes {
+      storage_balance: storage_balance_prefix,
+      upgrade_prices: upgrade_prices_prefix,
+      ft_whitelist: ft_whitelist_prefix,
+      sale_treasury: sale_treasury_prefix,
+      sale_phases: sale_phases_prefix,
+      sale_dutch: sale_dutch_prefix,
+      auction: auction_prefix,
+      market: market_prefix,
+      offer: offer_prefix,
+      sale_reservation: sale_reservation_prefix,
+      random_tokens_legacy: random_tokens_legacy_prefix,
+      sale_template: sale_template_prefix,
+      sale_drop: sale_drop_prefix,
+      pack: pack_prefix,
+      craft: craft_prefix,
+      fractionation_reversal: fractionation_reversal_prefix,
+      vault: vault_prefix,
+      royalty_defaults: royalty_defaults_prefix,
+      royalty_changes: royalty_changes_prefix,
+      transfer_fee: transfer_fee_prefix,
+      payout_claims: payout_claims_prefix,
+    } = prefixes;
     let mut this = Self {
       owner_id: owner_id.clone(),
       // royalty_account_id,
@@ -227,6 +380,73 @@ impl NonFungibleToken {
       fractionation_completed_by_id: fractionation_completed_prefix.map(LookupMap::new),
 
       bind_to_owner: BindToOwnerFeature::new(bind_to_owner_prefix),
+
+      storage_balance_by_id: LookupMap::new(storage_balance_prefix),
+
+      upgrade_prices: UnorderedMap::new(upgrade_prices_prefix),
+
+      ft_whitelist: FtWhitelistFeature::new(ft_whitelist_prefix),
+
+      sale_proceeds_by_id: LookupMap::new(sale_treasury_prefix.clone()),
+      sale_beneficiaries_by_id: LookupMap::new([sale_treasury_prefix, "b".into()].concat()),
+
+      sale_phases_by_id: LookupMap::new(sale_phases_prefix.clone()),
+      sale_phase_mint_counter: LookupMap::new([sale_phases_prefix, "c".into()].concat()),
+
+      sale_dutch_by_id: LookupMap::new(sale_dutch_prefix.clone()),
+      sale_dutch_purchases: LookupMap::new([sale_dutch_prefix.clone(), "p".into()].concat()),
+      sale_dutch_totals: LookupMap::new([sale_dutch_prefix, "t".into()].concat()),
+
+      auction_by_token: UnorderedMap::new(auction_prefix.clone()),
+      auctions_per_owner: LookupMap::new([auction_prefix, "o".into()].concat()),
+
+      listing_by_token: UnorderedMap::new(market_prefix.clone()),
+      listings_per_owner: LookupMap::new([market_prefix.clone(), "o".into()].concat()),
+      listings_per_collection: LookupMap::new([market_prefix.clone(), "c".into()].concat()),
+      listings_by_price: TreeMap::new([market_prefix, "p".into()].concat()),
+
+      offer_by_id: UnorderedMap::new(offer_prefix.clone()),
+      offers_per_target: LookupMap::new([offer_prefix.clone(), "t".into()].concat()),
+      offers_per_buyer: LookupMap::new([offer_prefix, "b".into()].concat()),
+      offers_next_id: 0,
+
+      sale_reservation_by_id: LookupMap::new(sale_reservation_prefix.clone()),
+      sale_reservations_per_account: LookupMap::new([sale_reservation_prefix.clone(), "a".into()].concat()),
+      sale_reserved_by_id: LookupMap::new([sale_reservation_prefix, "r".into()].concat()),
+      sale_reservations_next_id: 0,
+
+      sale_random_tokens_legacy: LookupMap::new(random_tokens_legacy_prefix),
+
+      sale_templates_by_id: LookupMap::new(sale_template_prefix),
+
+      sale_drop_table_by_id: LookupMap::new(sale_drop_prefix.clone()),
+      sale_drop_pools: LookupMap::new([sale_drop_prefix.clone(), "p".into()].concat()),
+      sale_drop_pity: LookupMap::new([sale_drop_prefix, "c".into()].concat()),
+
+      pack_by_token: LookupMap::new(pack_prefix),
+
+      recipe_by_id: UnorderedMap::new(craft_prefix.clone()),
+      recipe_crafted_per_account: LookupMap::new([craft_prefix.clone(), "a".into()].concat()),
+      craft_pending_by_id: LookupMap::new([craft_prefix, "p".into()].concat()),
+      craft_next_id: 0,
+
+      fractionation_reversal_by_id: LookupMap::new(fractionation_reversal_prefix.clone()),
+      fractionation_parts_by_id: LookupMap::new([fractionation_reversal_prefix, "p".into()].concat()),
+
+      vault_by_token: UnorderedMap::new(vault_prefix.clone()),
+      vault_shares: LookupMap::new([vault_prefix, "s".into()].concat()),
+
+      royalty_by_collection: LookupMap::new(royalty_defaults_prefix.clone()),
+      royalty_by_type: LookupMap::new([royalty_defaults_prefix, "t".into()].concat()),
+
+      royalty_proposal_by_id: LookupMap::new(royalty_changes_prefix.clone()),
+      royalty_history_by_id: LookupMap::new([royalty_changes_prefix, "h".into()].concat()),
+
+      transfer_fee_by_token: LookupMap::new(transfer_fee_prefix.clone()),
+      transfer_fee_by_collection: LookupMap::new([transfer_fee_prefix.clone(), "c".into()].concat()),
+      transfer_fee_exempt: UnorderedSet::new([transfer_fee_prefix, "e".into()].concat()),
+
+      payout_claims: LookupMap::new(payout_claims_prefix),
     };
     this.measure_min_token_storage_cost();
     this
@@ -317,8 +537,12 @@ impl NonFungibleTokenCore for NonFungibleToken {
   ) {
     // assert_one_yocto();
     let sender_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+
+    let (owner_id, _) = self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
 
-    self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+    let deposit = self.internal_storage_use_deposit(&sender_id, storage_used_since(initial_storage_usage), env::attached_deposit());
+    self.internal_transfer_fee_charge(&token_id, &sender_id, &owner_id, &receiver_id, None, deposit);
   }
 
   fn nft_transfer_call(
@@ -329,14 +553,24 @@ impl NonFungibleTokenCore for NonFungibleToken {
     memo: Option<String>,
     msg: String,
   ) -> PromiseOrValue<bool> {
-    assert_one_yocto();
+    // more than 1 yoctoNEAR is attached for transfer royalties
+    assert_at_least_one_yocto();
     require!(
             env::prepaid_gas() > GAS_FOR_NFT_TRANSFER_CALL + GAS_FOR_RESOLVE_TRANSFER,
             "More gas is required"
         );
     let sender_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
     let (old_owner, old_approvals) =
       self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+    let deposit = self.internal_storage_use_deposit(&sender_id, storage_used_since(initial_storage_usage), env::attached_deposit());
+
+    // royalties are paid by the resolver once the receiver keeps the token
+    let transfer_fee = self.assert_transfer_fee_deposit(&token_id, &old_owner, &receiver_id, None, deposit);
+    if transfer_fee.is_none() {
+      refund_transfer_deposit(&sender_id, deposit);
+    }
+
     // Initiating receiver's call and the callback
     ext_receiver::nft_on_transfer(
       sender_id,
@@ -352,6 +586,7 @@ impl NonFungibleTokenCore for NonFungibleToken {
         receiver_id,
         token_id,
         old_approvals,
+        transfer_fee,
         env::current_account_id(),
         NO_DEPOSIT,
         GAS_FOR_RESOLVE_TRANSFER,
@@ -375,6 +610,7 @@ impl NonFungibleTokenResolver for NonFungibleToken {
     receiver_id: AccountId,
     token_id: TokenId,
     approved_account_ids: Option<HashMap<AccountId, u64>>,
+    transfer_fee: Option<TransferFeeDeposit>,
   ) -> bool {
     // Get whether token should be returned
     let must_revert = match env::promise_result(0) {
@@ -391,6 +627,9 @@ impl NonFungibleTokenResolver for NonFungibleToken {
 
     // if call succeeded, return early
     if !must_revert {
+      if let Some(transfer_fee) = &transfer_fee {
+        self.internal_transfer_fee_pay(&token_id, &previous_owner_id, &receiver_id, transfer_fee);
+      }
       return true;
     }
 
@@ -400,11 +639,17 @@ impl NonFungibleTokenResolver for NonFungibleToken {
     if let Some(current_owner) = self.owner_by_id.get(&token_id) {
       if current_owner != receiver_id {
         // The token is not owned by the receiver anymore. Can't return it.
+        if let Some(transfer_fee) = &transfer_fee {
+          self.internal_transfer_fee_pay(&token_id, &previous_owner_id, &receiver_id, transfer_fee);
+        }
         return true;
       }
     } else {
       // The token was burned and doesn't exist anymore.
       // Refund storage cost for storing approvals to original owner and return early.
+      if let Some(transfer_fee) = &transfer_fee {
+        self.internal_transfer_fee_pay(&token_id, &previous_owner_id, &receiver_id, transfer_fee);
+      }
       if let Some(approved_account_ids) = approved_account_ids {
         refund_approved_account_ids(previous_owner_id, &approved_account_ids);
       }
@@ -415,6 +660,11 @@ impl NonFungibleTokenResolver for NonFungibleToken {
 
     self.internal_transfer_unguarded(&token_id, &receiver_id, &previous_owner_id);
 
+    // the transfer is reverted, royalties are not owed
+    if let Some(transfer_fee) = &transfer_fee {
+      refund_transfer_deposit(&transfer_fee.sender_id, transfer_fee.deposit.0);
+    }
+
     // If using Approval Management extension,
     // 1. revert any approvals receiver already set, refunding storage costs
     // 2. reset approvals to what previous owner had set before call to nft_transfer_call
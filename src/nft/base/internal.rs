@@ -24,6 +24,9 @@ impl NonFungibleToken {
   pub(crate) fn assert_available_transfer(&self, token_id: &TokenId, account_id: &AccountId) {
     self.bind_to_owner.assert_bind_to_player(&token_id);
     self.assert_token_not_in_sale(&token_id);
+    self.assert_token_not_in_auction(token_id);
+    self.assert_pack_not_opening(token_id);
+    self.assert_token_not_in_vault(token_id);
   }
 
   pub(crate) fn assert_available_approve(&self, token_id: &TokenId, account_id: &AccountId) {
@@ -41,6 +44,7 @@ impl NonFungibleToken {
   ) {
     // update owner
     self.owner_by_id.insert(token_id, to);
+    self.internal_market_remove(token_id);
 
     // if using Enumeration standard, update old & new owner's token lists
     if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
@@ -47,12 +47,14 @@ macro_rules! impl_non_fungible_token_core {
                 receiver_id: AccountId,
                 token_id: TokenId,
                 approved_account_ids: Option<std::collections::HashMap<AccountId, u64>>,
+                transfer_fee: Option<$crate::nft::transfer_fee::TransferFeeDeposit>,
             ) -> bool {
                 self.$token.nft_resolve_transfer(
                     previous_owner_id,
                     receiver_id,
                     token_id,
                     approved_account_ids,
+                    transfer_fee,
                 )
             }
         }
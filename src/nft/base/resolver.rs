@@ -1,6 +1,7 @@
 use crate::nft::token::TokenId;
 use near_sdk::AccountId;
 use std::collections::HashMap;
+use crate::nft::transfer_fee::TransferFeeDeposit;
 
 /// Used when an NFT is transferred using `nft_transfer_call`. This is the method that's called after `nft_on_transfer`. This trait is implemented on the NFT contract.
 pub trait NonFungibleTokenResolver {
@@ -28,6 +29,7 @@ pub trait NonFungibleTokenResolver {
   /// * `approvals`: if using Approval Management, contract MUST provide
   ///   set of original approved accounts in this argument, and restore these
   ///   approved accounts in case of revert.
+  /// * `transfer_fee`: royalties held from the deposit, paid if the transfer is kept and refunded otherwise
   ///
   /// Returns true if token was successfully transferred to `receiver_id`.
   fn nft_resolve_transfer(
@@ -36,5 +38,6 @@ pub trait NonFungibleTokenResolver {
     receiver_id: AccountId,
     token_id: TokenId,
     approvals: Option<HashMap<AccountId, u64>>,
+    transfer_fee: Option<TransferFeeDeposit>,
   ) -> bool;
 }
@@ -2,6 +2,7 @@ use near_sdk::collections::LookupMap;
 use crate::bind_to_owner::BindToOwnerMethods;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::IntoStorageKey;
+use crate::nft::{NonFungibleToken, TokenId};
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct BindToOwnerFeature {
@@ -35,6 +36,17 @@ impl BindToOwnerFeature {
   }
 }
 
+impl NonFungibleToken {
+  /// Bind the token and remove its market listing, a bound token cannot be sold
+  pub(crate) fn internal_token_bind_to_owner(&mut self, token_id: &TokenId, bind_to_owner: &bool) {
+    self.bind_to_owner.internal_token_bind_to_owner(token_id, bind_to_owner);
+
+    if *bind_to_owner {
+      self.internal_market_remove(token_id);
+    }
+  }
+}
+
 impl BindToOwnerMethods for BindToOwnerFeature {
   fn is_bind_to_owner(&self, token_id: String) -> bool {
     self.internal_is_bind_to_owner(&token_id)
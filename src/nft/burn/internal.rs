@@ -5,6 +5,9 @@ use crate::nft::events_171::NftBurn;
 impl NonFungibleToken {
   pub(crate) fn assert_available_burn(&self, token_id: &TokenId, account_id: &AccountId) {
     self.assert_token_not_fractionation(&token_id);
+    self.assert_token_not_in_auction(token_id);
+    self.assert_pack_not_opening(token_id);
+    self.assert_token_not_in_vault(token_id);
   }
 
   pub fn internal_burn_token(&mut self, sender_id: &AccountId, token_id: &TokenId)  {
@@ -34,6 +37,8 @@ impl NonFungibleToken {
     self.token_rarity_by_id.as_mut().unwrap().remove(&token_id);
 
     self.royalty.token_royalty_by_id.remove(&token_id);
+    self.royalty_proposal_by_id.remove(token_id);
+    self.transfer_fee_by_token.remove(token_id);
 
     self.token_metadata_by_id.as_mut().unwrap().remove(&token_id);
 
@@ -41,6 +46,12 @@ impl NonFungibleToken {
 
     self.sale_by_token.as_mut().unwrap().remove(&token_id);
 
+    self.internal_market_remove(token_id);
+
+    if let Some(pack) = self.internal_pack_remove(token_id) {
+      self.internal_pack_storage_settle(&pack, 0);
+    }
+
     self.approvals_by_id.as_mut().unwrap().remove(&token_id);
 
     self.bind_to_owner.token_bind_by_id.remove(&token_id);
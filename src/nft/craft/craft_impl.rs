@@ -0,0 +1,100 @@
+use near_sdk::{env, require, AccountId};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, storage_used_since};
+use crate::nft::craft::{NonFungibleTokenCraft, NonFungibleTokenCraftEnumeration, Recipe, RecipeId, RecipeInput, PendingCraft, CraftId};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::events_171_mf::RecipeUpdate;
+
+impl NonFungibleTokenCraft for NonFungibleToken {
+  fn nft_recipe_add(&mut self, recipe: Recipe) -> Recipe {
+    self.assert_owner();
+
+    assert!(self.recipe_by_id.get(&recipe.id).is_none(), "Recipe exists");
+    assert!(!recipe.inputs.is_empty(), "Recipe requires inputs");
+    recipe.inputs.iter().for_each(|input| {
+      if let RecipeInput::Kind { count, .. } = input {
+        assert!(*count > 0, "Recipe input count must be greater than 0");
+      }
+    });
+    assert!(recipe.output.amount > 0, "Recipe output amount must be greater than 0");
+    assert!(recipe.output.rarity_weights.iter().map(|(_, weight)| weight).sum::<u32>() > 0, "Recipe output requires rarity weights");
+    recipe.output.metadata.assert_valid();
+
+    if let Some(ft_token) = &recipe.ft_token {
+      self.ft_whitelist.assert_whitelisted(&FtPurpose::Craft, ft_token);
+    }
+
+    let mut recipe = recipe;
+    recipe.crafted = 0;
+    recipe.output.minted = 0;
+
+    self.recipe_by_id.insert(&recipe.id, &recipe);
+
+    RecipeUpdate {
+      recipe: &recipe,
+    }.emit();
+
+    recipe
+  }
+
+  fn nft_recipe_enable(&mut self, recipe_id: RecipeId, enabled: bool) -> Recipe {
+    self.assert_owner();
+
+    let mut recipe = self.recipe_by_id.get(&recipe_id).expect("Not found recipe");
+    recipe.enabled = enabled;
+    self.recipe_by_id.insert(&recipe_id, &recipe);
+
+    RecipeUpdate {
+      recipe: &recipe,
+    }.emit();
+
+    recipe
+  }
+
+  fn nft_craft(&mut self, recipe_id: RecipeId, token_ids: Vec<TokenId>) -> PendingCraft {
+    let account_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+
+    let craft = self.internal_craft(&account_id, &recipe_id, token_ids, None, env::attached_deposit());
+    let fee = self.recipe_by_id.get(&recipe_id).unwrap().fee.0;
+
+    self.internal_storage_charge(&account_id, storage_used_since(initial_storage_usage) + craft.storage, fee);
+
+    craft
+  }
+
+  fn nft_craft_reveal(&mut self, craft_id: CraftId) -> TokenId {
+    self.internal_craft_reveal(&craft_id)
+  }
+}
+
+impl NonFungibleTokenCraftEnumeration for NonFungibleToken {
+  fn nft_recipe(&self, recipe_id: RecipeId) -> Option<Recipe> {
+    self.recipe_by_id.get(&recipe_id)
+  }
+
+  fn nft_recipes(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Recipe> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.recipe_by_id.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.recipe_by_id
+      .values()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+
+  fn nft_recipe_crafted(&self, recipe_id: RecipeId, account_id: AccountId) -> u32 {
+    self.recipe_crafted_per_account.get(&(recipe_id, account_id)).unwrap_or(0)
+  }
+
+  fn nft_craft_pending(&self, craft_id: CraftId) -> Option<PendingCraft> {
+    self.craft_pending_by_id.get(&craft_id)
+  }
+}
@@ -0,0 +1,266 @@
+use near_sdk::{AccountId, env, ext_contract, Balance, Gas};
+use crate::nft::{NonFungibleToken, TokenId, random_use, transfer_near_or_ft, storage_used_since};
+use crate::nft::craft::{CraftId, PendingCraft, Recipe, RecipeId, RecipeInput};
+use crate::nft::events_171_mf::{Craft, CraftComplete};
+
+const GAS_FOR_CRAFT_REVEAL: Gas = Gas(20_000_000_000_000);
+const NO_DEPOSIT: Balance = 0;
+
+#[ext_contract(ext_self)]
+trait CraftRevealer {
+  fn nft_craft_reveal(&mut self, craft_id: CraftId) -> TokenId;
+}
+
+impl NonFungibleToken {
+  fn internal_recipe_input_matches(&self, input: &RecipeInput, token_id: &TokenId) -> bool {
+    match input {
+      RecipeInput::Token { token_id: input_token_id } => input_token_id == token_id,
+      RecipeInput::Kind { token_type, token_sub_type, rarity, collection, .. } => {
+        (token_type.is_none() || &self.token_type_by_id.as_ref().unwrap().get(token_id) == token_type)
+          && (token_sub_type.is_none() || &self.token_sub_type_by_id.as_ref().unwrap().get(token_id) == token_sub_type)
+          && (rarity.is_none() || &self.token_rarity_by_id.as_ref().unwrap().get(token_id) == rarity)
+          && (collection.is_none() || &self.token_collection_by_id.as_ref().unwrap().get(token_id) == collection)
+      }
+    }
+  }
+
+  /// Every token fills exactly one input and every input is filled
+  pub(crate) fn assert_recipe_inputs(&self, recipe: &Recipe, token_ids: &[TokenId]) {
+    let mut rest: Vec<&TokenId> = token_ids.iter().collect();
+
+    recipe.inputs.iter().for_each(|input| {
+      if let RecipeInput::Token { token_id } = input {
+        let index = rest.iter().position(|rest_id| *rest_id == token_id).unwrap_or_else(|| {
+          env::panic_str(&format!("Recipe input {} is missing", token_id))
+        });
+        rest.remove(index);
+      }
+    });
+
+    let counts: Vec<u32> = recipe.inputs
+      .iter()
+      .map(|input| match input {
+        RecipeInput::Token { .. } => 0,
+        RecipeInput::Kind { count, .. } => *count,
+      })
+      .collect();
+
+    let matches: Vec<Vec<bool>> = rest
+      .iter()
+      .map(|token_id| {
+        let token_matches: Vec<bool> = recipe.inputs
+          .iter()
+          .zip(counts.iter())
+          .map(|(input, count)| *count > 0 && self.internal_recipe_input_matches(input, token_id))
+          .collect();
+
+        if !token_matches.contains(&true) {
+          env::panic_str(&format!("Token {} does not match the recipe", token_id));
+        }
+
+        token_matches
+      })
+      .collect();
+
+    let open: u32 = counts.iter().sum();
+    assert!(rest.len() as u32 >= open, "Not enough tokens for the recipe");
+    assert!(rest.len() as u32 <= open, "Too many tokens for the recipe");
+    assert!(recipe_inputs_fill(&matches, &counts), "Tokens do not fill the recipe inputs");
+  }
+
+  /// Burn the inputs and take the fee of `amount` in `ft_token`, the output is minted by a scheduled reveal
+  pub(crate) fn internal_craft(&mut self, account_id: &AccountId, recipe_id: &RecipeId, token_ids: Vec<TokenId>, ft_token: Option<AccountId>, amount: Balance) -> PendingCraft {
+    let mut recipe = self.recipe_by_id.get(recipe_id).expect("Not found recipe");
+
+    assert!(recipe.enabled, "Recipe is disabled");
+    assert_eq!(recipe.ft_token, ft_token, "Invalid recipe currency");
+    assert!(amount >= recipe.fee.0, "Invalid attached fee");
+    assert!(recipe.crafted < recipe.output.amount, "Recipe output limit");
+
+    let key = (recipe_id.clone(), account_id.clone());
+    let account_crafted = self.recipe_crafted_per_account.get(&key).unwrap_or(0);
+
+    if let Some(account_max) = recipe.account_max {
+      assert!(account_crafted < account_max, "Craft limit");
+    }
+
+    token_ids.iter().enumerate().for_each(|(index, token_id)| {
+      assert!(!token_ids[..index].contains(token_id), "Token {} is duplicated", token_id);
+
+      let owner_id = self.owner_by_id.get(token_id).expect("Not found token");
+      assert_eq!(&owner_id, account_id, "Only owner can craft");
+    });
+    self.assert_recipe_inputs(&recipe, &token_ids);
+
+    token_ids.iter().for_each(|token_id| {
+      self.assert_available_burn(token_id, account_id);
+      self.assert_token_not_in_sale(token_id);
+      self.internal_burn_token_unguarded(account_id, token_id);
+    });
+
+    recipe.crafted += 1;
+    self.recipe_by_id.insert(recipe_id, &recipe);
+    self.recipe_crafted_per_account.insert(&key, &(account_crafted + 1));
+
+    let craft = PendingCraft {
+      id: self.craft_next_id,
+      recipe_id: recipe_id.clone(),
+      account_id: account_id.clone(),
+      block_height: env::block_height(),
+      storage: self.internal_template_storage(&recipe.output),
+    };
+    self.craft_next_id += 1;
+    self.craft_pending_by_id.insert(&craft.id, &craft);
+
+    if recipe.fee.0 > 0 {
+      transfer_near_or_ft(&recipe.ft_token, &self.owner_id, recipe.fee.0, format!("Recipe {} fee", recipe_id));
+    }
+
+    Craft {
+      craft_id: &craft.id,
+      recipe_id,
+      account_id,
+      token_ids: &token_ids,
+    }.emit();
+
+    // minted in a later block, its random seed is not known while the inputs are burned
+    ext_self::nft_craft_reveal(
+      craft.id,
+      env::current_account_id(),
+      NO_DEPOSIT,
+      GAS_FOR_CRAFT_REVEAL,
+    );
+
+    craft
+  }
+
+  pub(crate) fn internal_craft_reveal(&mut self, craft_id: &CraftId) -> TokenId {
+    let craft = self.craft_pending_by_id.get(craft_id).expect("Not found craft");
+
+    assert!(env::block_height() > craft.block_height, "Craft can be revealed only in a later block");
+
+    self.craft_pending_by_id.remove(craft_id);
+
+    let mut recipe = self.recipe_by_id.get(&craft.recipe_id).expect("Not found recipe");
+    let prefix = format!("{}:craft", recipe.id);
+    let initial_storage_usage = env::storage_usage();
+    let token_id = self.internal_template_mint(&mut recipe.output, &prefix, &craft.account_id, &mut random_use());
+    self.internal_storage_settle(&craft.account_id, craft.storage, storage_used_since(initial_storage_usage));
+
+    self.recipe_by_id.insert(&recipe.id, &recipe);
+
+    CraftComplete {
+      craft_id,
+      recipe_id: &craft.recipe_id,
+      account_id: &craft.account_id,
+      token_id: &token_id,
+    }.emit();
+
+    token_id
+  }
+}
+
+/// Whether every token fills one of `counts` input slots and every slot is filled, `matches[token][input]`
+/// tells if the token fits the input. A token may fit several inputs, so a taken slot is given up when
+/// its token can move to another one (augmenting paths of a bipartite matching).
+pub(crate) fn recipe_inputs_fill(matches: &[Vec<bool>], counts: &[u32]) -> bool {
+  let slots: Vec<usize> = counts
+    .iter()
+    .enumerate()
+    .flat_map(|(input, count)| (0..*count).map(move |_| input))
+    .collect();
+
+  if slots.len() != matches.len() {
+    return false;
+  }
+
+  let mut slot_tokens: Vec<Option<usize>> = vec![None; slots.len()];
+
+  (0..matches.len()).all(|token| {
+    let mut visited = vec![false; slots.len()];
+    recipe_input_augment(matches, &slots, token, &mut visited, &mut slot_tokens)
+  })
+}
+
+fn recipe_input_augment(matches: &[Vec<bool>], slots: &[usize], token: usize, visited: &mut [bool], slot_tokens: &mut [Option<usize>]) -> bool {
+  for slot in 0..slots.len() {
+    if visited[slot] || !matches[token][slots[slot]] {
+      continue;
+    }
+    visited[slot] = true;
+
+    let free = match slot_tokens[slot] {
+      Some(other) => recipe_input_augment(matches, slots, other, visited, slot_tokens),
+      None => true,
+    };
+
+    if free {
+      slot_tokens[slot] = Some(token);
+      return true;
+    }
+  }
+
+  false
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::prelude::*;
+
+  /// Try every input for every token, fine for a few tokens
+  fn fill_exhaustive(matches: &[Vec<bool>], counts: &mut [u32], token: usize) -> bool {
+    if token == matches.len() {
+      return counts.iter().all(|count| *count == 0);
+    }
+
+    (0..counts.len()).any(|input| {
+      if !matches[token][input] || counts[input] == 0 {
+        return false;
+      }
+
+      counts[input] -= 1;
+      let filled = fill_exhaustive(matches, counts, token + 1);
+      counts[input] += 1;
+
+      filled
+    })
+  }
+
+  #[test]
+  fn rare_token_moves_to_the_input_only_it_fits() {
+    // inputs: 2 of any Nordic, 1 of Nordic Rare; tokens: rare, rare, common
+    let matches = vec![vec![true, true], vec![true, true], vec![true, false]];
+
+    assert!(recipe_inputs_fill(&matches, &[2, 1]));
+  }
+
+  #[test]
+  fn common_tokens_do_not_fill_the_rare_input() {
+    let matches = vec![vec![true, false], vec![true, false], vec![true, false]];
+
+    assert!(!recipe_inputs_fill(&matches, &[2, 1]));
+  }
+
+  #[test]
+  fn token_count_must_match_slots() {
+    let matches = vec![vec![true], vec![true]];
+
+    assert!(!recipe_inputs_fill(&matches, &[1]));
+    assert!(!recipe_inputs_fill(&matches, &[3]));
+  }
+
+  proptest! {
+    #[test]
+    fn matching_agrees_with_exhaustive_search(
+      (counts, matches) in prop::collection::vec(0u32..3, 1..4).prop_flat_map(|counts| {
+        let inputs = counts.len();
+        let tokens = counts.iter().sum::<u32>() as usize;
+
+        (Just(counts), prop::collection::vec(prop::collection::vec(any::<bool>(), inputs), tokens))
+      })
+    ) {
+      prop_assert_eq!(recipe_inputs_fill(&matches, &counts), fill_exhaustive(&matches, &mut counts.clone(), 0));
+    }
+  }
+}
@@ -0,0 +1,50 @@
+// Craft
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_craft {
+    ($contract: ident, $tokens: ident $(, $assert_burn: ident)?) => {
+        use $crate::nft::craft::{NonFungibleTokenCraft, NonFungibleTokenCraftEnumeration, Recipe, RecipeId, PendingCraft, CraftId};
+
+        #[near_bindgen]
+        impl NonFungibleTokenCraft for $contract {
+          fn nft_recipe_add(&mut self, recipe: Recipe) -> Recipe {
+            self.$tokens.nft_recipe_add(recipe)
+          }
+
+          fn nft_recipe_enable(&mut self, recipe_id: RecipeId, enabled: bool) -> Recipe {
+            self.$tokens.nft_recipe_enable(recipe_id, enabled)
+          }
+
+          #[payable]
+          fn nft_craft(&mut self, recipe_id: RecipeId, token_ids: Vec<TokenId>) -> PendingCraft {
+            $(token_ids.iter().for_each(|token_id| self.$assert_burn(token_id));)?
+
+            self.$tokens.nft_craft(recipe_id, token_ids)
+          }
+
+          #[private]
+          fn nft_craft_reveal(&mut self, craft_id: CraftId) -> TokenId {
+            self.$tokens.nft_craft_reveal(craft_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenCraftEnumeration for $contract {
+          fn nft_recipe(&self, recipe_id: RecipeId) -> Option<Recipe> {
+            self.$tokens.nft_recipe(recipe_id)
+          }
+
+          fn nft_recipes(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Recipe> {
+            self.$tokens.nft_recipes(from_index, limit)
+          }
+
+          fn nft_recipe_crafted(&self, recipe_id: RecipeId, account_id: AccountId) -> u32 {
+            self.$tokens.nft_recipe_crafted(recipe_id, account_id)
+          }
+
+          fn nft_craft_pending(&self, craft_id: CraftId) -> Option<PendingCraft> {
+            self.$tokens.nft_craft_pending(craft_id)
+          }
+        }
+    };
+}
@@ -0,0 +1,50 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use crate::nft::{TokenId, TokenType, TokenSubType, TokenRarity, TokenCollection};
+use crate::nft::sale::SaleTemplate;
+
+pub type RecipeId = String;
+pub type CraftId = u64;
+
+/// `Kind` input takes `count` tokens that match all of the set fields
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RecipeInput {
+  Token { token_id: TokenId },
+  Kind {
+    token_type: Option<TokenType>,
+    token_sub_type: Option<TokenSubType>,
+    rarity: Option<TokenRarity>,
+    collection: Option<TokenCollection>,
+    count: u32,
+  },
+}
+
+/// `fee` is paid in `ft_token` or in NEAR when it is `None`, `output.amount` limits crafts of the recipe
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Recipe {
+  pub id: RecipeId,
+  pub inputs: Vec<RecipeInput>,
+  pub output: SaleTemplate,
+  pub ft_token: Option<AccountId>,
+  pub fee: U128,
+  pub account_max: Option<u32>,
+  pub enabled: bool,
+  #[serde(default)]
+  pub crafted: u64,
+}
+
+/// Inputs are burned, the output is minted in a later block than `block_height`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingCraft {
+  pub id: CraftId,
+  pub recipe_id: RecipeId,
+  pub account_id: AccountId,
+  pub block_height: u64,
+  // bytes paid for the output, the unused part is refunded at reveal
+  pub storage: u64,
+}
@@ -0,0 +1,33 @@
+pub use metadata::*;
+use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::TokenId;
+
+pub mod metadata;
+pub mod craft_impl;
+mod internal;
+mod macros;
+
+/// Recipes burn input tokens for a token minted from the recipe output template.
+/// Token inputs are matched first, the rest of tokens fill `Kind` inputs in the given order.
+pub trait NonFungibleTokenCraft {
+  fn nft_recipe_add(&mut self, recipe: Recipe) -> Recipe;
+
+  fn nft_recipe_enable(&mut self, recipe_id: RecipeId, enabled: bool) -> Recipe;
+
+  /// Burn the tokens of the caller and pay the attached fee, the output is minted by `nft_craft_reveal`
+  fn nft_craft(&mut self, recipe_id: RecipeId, token_ids: Vec<TokenId>) -> PendingCraft;
+
+  /// The reveal is scheduled by the craft itself, only the contract account can retry it
+  fn nft_craft_reveal(&mut self, craft_id: CraftId) -> TokenId;
+}
+
+pub trait NonFungibleTokenCraftEnumeration {
+  fn nft_recipe(&self, recipe_id: RecipeId) -> Option<Recipe>;
+
+  fn nft_recipes(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Recipe>;
+
+  fn nft_recipe_crafted(&self, recipe_id: RecipeId, account_id: AccountId) -> u32;
+
+  fn nft_craft_pending(&self, craft_id: CraftId) -> Option<PendingCraft>;
+}
@@ -0,0 +1,45 @@
+use crate::nft::{NonFungibleToken, TokenId, bytes_for_approved_account_id, storage_cost};
+use crate::nft::deposit::NonFungibleTokenDeposit;
+use crate::nft::metadata::TokenMetadata;
+use crate::SaleId;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+
+// storage key prefix, borsh length of the key and the trie overhead of a record
+const BYTES_PER_RECORD: u64 = 2 + 4 + 40;
+// reservation record and its entry in reservations of the receiver, payer and receiver ids of up to 64 bytes
+const BYTES_PER_RESERVATION: u64 = 3 * BYTES_PER_RECORD + 2 * 64 + 128;
+
+impl NonFungibleTokenDeposit for NonFungibleToken {
+  fn nft_mint_deposit(&self, token_metadata: TokenMetadata) -> U128 {
+    let metadata_bytes = token_metadata.try_to_vec().unwrap().len() as u64;
+
+    U128::from(storage_cost(self.extra_storage_in_bytes_per_token + metadata_bytes))
+  }
+
+  fn nft_approve_deposit(&self, token_id: TokenId, account_id: AccountId) -> U128 {
+    // approvals are not stored when the extension is disabled
+    let approvals = match &self.approvals_by_id {
+      Some(approvals_by_id) => approvals_by_id.get(&token_id),
+      None => return U128::from(0),
+    };
+
+    let bytes = match approvals {
+      Some(approvals) if approvals.contains_key(&account_id) => 0,
+      Some(_) => bytes_for_approved_account_id(&account_id),
+      // new records in approvals_by_id and next_approval_id_by_id
+      None => bytes_for_approved_account_id(&account_id) + 2 * (BYTES_PER_RECORD + token_id.len() as u64) + 4 + 8,
+    };
+
+    U128::from(storage_cost(bytes))
+  }
+
+  fn nft_buy_deposit(&self, sale_id: SaleId, amount: u64) -> U128 {
+    // price of the current phase or of the dutch auction, storage of revealed tokens and the reservation
+    let price = self.internal_sale_price(&sale_id);
+    let storage = storage_cost(self.internal_sale_token_storage(&sale_id) * amount + BYTES_PER_RESERVATION);
+
+    U128::from(price * (amount as u128) + storage)
+  }
+}
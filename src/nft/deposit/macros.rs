@@ -0,0 +1,23 @@
+// Deposit
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_deposit {
+    ($contract: ident, $tokens: ident) => {
+        use $crate::{NonFungibleTokenDeposit};
+
+        #[near_bindgen]
+        impl NonFungibleTokenDeposit for $contract {
+          fn nft_mint_deposit(&self, token_metadata: TokenMetadata) -> U128 {
+            self.$tokens.nft_mint_deposit(token_metadata)
+          }
+
+          fn nft_approve_deposit(&self, token_id: TokenId, account_id: AccountId) -> U128 {
+            self.$tokens.nft_approve_deposit(token_id, account_id)
+          }
+
+          fn nft_buy_deposit(&self, sale_id: SaleId, amount: u64) -> U128 {
+            self.$tokens.nft_buy_deposit(sale_id, amount)
+          }
+        }
+    };
+}
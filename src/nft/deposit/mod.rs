@@ -0,0 +1,21 @@
+use crate::nft::TokenId;
+use crate::nft::metadata::TokenMetadata;
+use crate::SaleId;
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+
+pub mod deposit_impl;
+mod macros;
+
+/// Quotes for the deposit a call has to attach. The actual storage is measured
+/// during the call and any excess deposit is refunded to the caller.
+pub trait NonFungibleTokenDeposit {
+  /// Deposit required by `nft_mint` to store a token with `token_metadata`
+  fn nft_mint_deposit(&self, token_metadata: TokenMetadata) -> U128;
+
+  /// Deposit required by `nft_approve` to add `account_id` to approvals of `token_id`
+  fn nft_approve_deposit(&self, token_id: TokenId, account_id: AccountId) -> U128;
+
+  /// Deposit required by `nft_buy`, price of `amount` tokens included
+  fn nft_buy_deposit(&self, sale_id: SaleId, amount: u64) -> U128;
+}
@@ -16,7 +16,7 @@ impl NonFungibleToken {
     // custom
     let bind_to_owner = self.bind_to_owner.token_bind_by_id.get(&token_id);
     let rarity = self.token_rarity_by_id.as_ref().unwrap().get(&token_id);
-    let royalty = self.royalty.token_royalty_by_id.get(&token_id);
+    let royalty = Some(self.internal_token_royalty(&token_id));
     let collection = self.token_collection_by_id.as_ref().unwrap().get(&token_id);
     let token_type = self.token_type_by_id.as_ref().unwrap().get(&token_id);
     let token_sub_type = self.token_sub_type_by_id.as_ref().unwrap().get(&token_id);
@@ -145,8 +145,8 @@ mod tests {
   #[test]
   fn nft_mint() {
     let owner_id = &bob();
-    let token_ids = &["0", "1"];
-    NftMint { owner_id, token_ids, memo: None, sale_id: &"test".to_string() }.emit();
+    let token_ids = &vec!["0".to_string(), "1".to_string()];
+    NftMint { owner_id, token_ids, memo: None, sale_id: None }.emit();
     assert_eq!(
       test_utils::get_logs()[0],
       r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"bob","token_ids":["0","1"]}]}"#
@@ -156,11 +156,11 @@ mod tests {
   #[test]
   fn nft_mints() {
     let owner_id = &bob();
-    let token_ids = &["0", "1"];
-    let mint_log = NftMint { owner_id, token_ids, memo: None, sale_id: &"test".to_string() };
+    let token_ids = &vec!["0".to_string(), "1".to_string()];
+    let mint_log = NftMint { owner_id, token_ids, memo: None, sale_id: None };
     NftMint::emit_many(&[
       mint_log,
-      NftMint { owner_id: &alice(), token_ids: &["2", "3"], memo: Some("has memo"), sale_id: &"test".to_string() },
+      NftMint { owner_id: &alice(), token_ids: &vec!["2".to_string(), "3".to_string()], memo: Some("has memo"), sale_id: None },
     ]);
     assert_eq!(
       test_utils::get_logs()[0],
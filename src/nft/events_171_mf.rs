@@ -1,7 +1,17 @@
 use crate::event::NearEvent;
 use near_sdk::AccountId;
 use serde::Serialize;
-use crate::nft::{TokenId, JsonSale, SaleId, Token, TokenRarity};
+use crate::nft::{TokenId, JsonSale, SaleId, Token, TokenRarity, TokenType, TokenCollection, Royalty};
+use crate::nft::sale::{SalePhase, SaleSettle, SaleReservation, SaleReservationId, SaleTemplate, DropTable};
+use crate::nft::auction::Auction;
+use crate::nft::market::Listing;
+use crate::nft::offer::{Offer, OfferId};
+use crate::nft::pack::Pack;
+use crate::nft::fractionation::FractionationReversal;
+use crate::nft::vault::Vault;
+use crate::nft::royalty::RoyaltyProposal;
+use crate::nft::transfer_fee::TransferFee;
+use crate::nft::craft::{Recipe, RecipeId, CraftId};
 use near_sdk::json_types::U128;
 
 // #
@@ -121,6 +131,41 @@ impl FractionationComplete<'_> {
   }
 }
 
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct FractionationReversalUpdate<'a> {
+  pub token_id: &'a TokenId,
+  pub reversal: Option<&'a FractionationReversal>,
+}
+
+impl FractionationReversalUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[FractionationReversalUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::FractionationReversalUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct FractionationSplit<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+  pub token_ids: &'a [TokenId],
+}
+
+impl FractionationSplit<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[FractionationSplit<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::FractionationSplit(data)).emit()
+  }
+}
+
 // #
 
 #[must_use]
@@ -165,6 +210,7 @@ impl SaleCreate<'_> {
 pub struct SaleStart<'a> {
   pub sale_id: &'a SaleId,
   pub date: &'a u64,
+  pub end_date: Option<&'a u64>,
 }
 
 impl SaleStart<'_> {
@@ -182,6 +228,7 @@ impl SaleStart<'_> {
 pub struct SaleUpdate<'a> {
   pub sale_id: &'a SaleId,
   pub date: &'a u64,
+  pub end_date: Option<&'a u64>,
   pub per_transaction_max: &'a u32,
   pub per_transaction_min: &'a u32,
   pub buy_max: &'a u32,
@@ -214,6 +261,641 @@ impl SalePause<'_> {
   }
 }
 
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct UpgradePriceUpdate<'a> {
+  pub ft_token_id: &'a AccountId,
+  pub token_type: &'a TokenType,
+  pub rarity: &'a TokenRarity,
+  pub price: Option<&'a U128>,
+}
+
+impl UpgradePriceUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[UpgradePriceUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::UpgradePriceUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct AuctionCreate<'a> {
+  pub auction: &'a Auction,
+}
+
+impl AuctionCreate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[AuctionCreate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::AuctionCreate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct AuctionBid<'a> {
+  pub token_id: &'a TokenId,
+  pub account_id: &'a AccountId,
+  pub amount: &'a U128,
+  pub end_date: &'a u64,
+}
+
+impl AuctionBid<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[AuctionBid<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::AuctionBid(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct AuctionCancel<'a> {
+  pub token_id: &'a TokenId,
+}
+
+impl AuctionCancel<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[AuctionCancel<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::AuctionCancel(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct AuctionSettle<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+  pub winner_id: Option<&'a AccountId>,
+  pub amount: Option<&'a U128>,
+}
+
+impl AuctionSettle<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[AuctionSettle<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::AuctionSettle(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct MarketList<'a> {
+  pub listing: &'a Listing,
+}
+
+impl MarketList<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[MarketList<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::MarketList(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct MarketRemove<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+}
+
+impl MarketRemove<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[MarketRemove<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::MarketRemove(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct MarketBuy<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+  pub buyer_id: &'a AccountId,
+  pub receiver_id: &'a AccountId,
+  pub ft_token: Option<&'a AccountId>,
+  pub price: &'a U128,
+}
+
+impl MarketBuy<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[MarketBuy<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::MarketBuy(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct OfferMake<'a> {
+  pub offer: &'a Offer,
+}
+
+impl OfferMake<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[OfferMake<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::OfferMake(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct OfferCancel<'a> {
+  pub offer_id: &'a OfferId,
+  pub buyer_id: &'a AccountId,
+}
+
+impl OfferCancel<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[OfferCancel<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::OfferCancel(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct OfferAccept<'a> {
+  pub offer_id: &'a OfferId,
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+  pub buyer_id: &'a AccountId,
+  pub amount: &'a U128,
+}
+
+impl OfferAccept<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[OfferAccept<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::OfferAccept(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct PackCreate<'a> {
+  pub pack: &'a Pack,
+}
+
+impl PackCreate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[PackCreate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::PackCreate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct PackOpen<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+}
+
+impl PackOpen<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[PackOpen<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::PackOpen(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct PackReveal<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+  pub sale_id: &'a SaleId,
+  pub token_ids: &'a [TokenId],
+}
+
+impl PackReveal<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[PackReveal<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::PackReveal(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct RecipeUpdate<'a> {
+  pub recipe: &'a Recipe,
+}
+
+impl RecipeUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[RecipeUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::RecipeUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct Craft<'a> {
+  pub craft_id: &'a CraftId,
+  pub recipe_id: &'a RecipeId,
+  pub account_id: &'a AccountId,
+  pub token_ids: &'a [TokenId],
+}
+
+impl Craft<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[Craft<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::Craft(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct CraftComplete<'a> {
+  pub craft_id: &'a CraftId,
+  pub recipe_id: &'a RecipeId,
+  pub account_id: &'a AccountId,
+  pub token_id: &'a TokenId,
+}
+
+impl CraftComplete<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[CraftComplete<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::CraftComplete(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct VaultCreate<'a> {
+  pub vault: &'a Vault,
+}
+
+impl VaultCreate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[VaultCreate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::VaultCreate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct VaultTransfer<'a> {
+  pub token_id: &'a TokenId,
+  pub old_owner_id: &'a AccountId,
+  pub new_owner_id: &'a AccountId,
+  pub amount: &'a U128,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub memo: Option<&'a str>,
+}
+
+impl VaultTransfer<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[VaultTransfer<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::VaultTransfer(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct VaultBuyout<'a> {
+  pub token_id: &'a TokenId,
+  pub buyer_id: &'a AccountId,
+  pub receiver_id: &'a AccountId,
+  pub ft_token: Option<&'a AccountId>,
+  pub price: &'a U128,
+}
+
+impl VaultBuyout<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[VaultBuyout<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::VaultBuyout(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct VaultRedeem<'a> {
+  pub token_id: &'a TokenId,
+  pub account_id: &'a AccountId,
+  pub shares: &'a U128,
+  pub amount: &'a U128,
+}
+
+impl VaultRedeem<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[VaultRedeem<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::VaultRedeem(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct RoyaltyDefaultUpdate<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub collection: Option<&'a TokenCollection>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub token_type: Option<&'a TokenType>,
+  pub royalty: Option<&'a Royalty>,
+}
+
+impl RoyaltyDefaultUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[RoyaltyDefaultUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::RoyaltyDefaultUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct RoyaltyUpdate<'a> {
+  pub token_id: &'a TokenId,
+  pub previous: &'a Royalty,
+  pub royalty: &'a Royalty,
+  pub changed_by: &'a AccountId,
+}
+
+impl RoyaltyUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[RoyaltyUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::RoyaltyUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct RoyaltyPropose<'a> {
+  pub token_id: &'a TokenId,
+  pub proposal: &'a RoyaltyProposal,
+}
+
+impl RoyaltyPropose<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[RoyaltyPropose<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::RoyaltyPropose(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct TransferFeeUpdate<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub collection: Option<&'a TokenCollection>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub token_id: Option<&'a TokenId>,
+  pub fee: Option<&'a TransferFee>,
+}
+
+impl TransferFeeUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[TransferFeeUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::TransferFeeUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct TransferRoyalty<'a> {
+  pub token_id: &'a TokenId,
+  pub owner_id: &'a AccountId,
+  pub receiver_id: &'a AccountId,
+  pub price: &'a U128,
+  pub royalties: &'a U128,
+}
+
+impl TransferRoyalty<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[TransferRoyalty<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::TransferRoyalty(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleEnd<'a> {
+  pub sale_id: &'a SaleId,
+  pub settle: &'a SaleSettle,
+  pub token_ids: &'a [TokenId],
+}
+
+impl SaleEnd<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleEnd<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleEnd(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SalePhasesUpdate<'a> {
+  pub sale_id: &'a SaleId,
+  pub phases: &'a [SalePhase],
+}
+
+impl SalePhasesUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SalePhasesUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SalePhasesUpdate(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleRevenue<'a> {
+  pub sale_id: &'a SaleId,
+  pub ft_token: Option<&'a AccountId>,
+  pub amount: &'a U128,
+}
+
+impl SaleRevenue<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleRevenue<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleRevenue(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleWithdraw<'a> {
+  pub sale_id: &'a SaleId,
+  pub ft_token: Option<&'a AccountId>,
+  pub receiver_id: &'a AccountId,
+  pub amount: &'a U128,
+}
+
+impl SaleWithdraw<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleWithdraw<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleWithdraw(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleReserve<'a> {
+  pub reservation: &'a SaleReservation,
+}
+
+impl SaleReserve<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleReserve<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleReserve(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleReveal<'a> {
+  pub reservation_id: &'a SaleReservationId,
+  pub sale_id: &'a SaleId,
+  pub receiver_id: &'a AccountId,
+  pub token_ids: &'a [TokenId],
+}
+
+impl SaleReveal<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleReveal<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleReveal(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleRefund<'a> {
+  pub reservation_id: &'a SaleReservationId,
+  pub sale_id: &'a SaleId,
+  pub payer_id: &'a AccountId,
+  pub ft_token: Option<&'a AccountId>,
+  pub amount: &'a U128,
+}
+
+impl SaleRefund<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleRefund<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleRefund(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleTemplateAdd<'a> {
+  pub sale_id: &'a SaleId,
+  pub template: &'a SaleTemplate,
+}
+
+impl SaleTemplateAdd<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleTemplateAdd<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleTemplateAdd(data)).emit()
+  }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct SaleDropTableUpdate<'a> {
+  pub sale_id: &'a SaleId,
+  pub drop_table: Option<&'a DropTable>,
+}
+
+impl SaleDropTableUpdate<'_> {
+  pub fn emit(self) {
+    Self::emit_many(&[self])
+  }
+
+  pub fn emit_many(data: &[SaleDropTableUpdate<'_>]) {
+    new_171_mf_v1(Nep171MfEventKind::SaleDropTableUpdate(data)).emit()
+  }
+}
+
 // #
 
 
@@ -235,12 +917,49 @@ enum Nep171MfEventKind<'a> {
   FractionationCreate(&'a [FractionationCreate<'a>]),
   FractionationAddToken(&'a [FractionationAddToken<'a>]),
   FractionationComplete(&'a [FractionationComplete<'a>]),
+  FractionationReversalUpdate(&'a [FractionationReversalUpdate<'a>]),
+  FractionationSplit(&'a [FractionationSplit<'a>]),
   SaleCreate(&'a [SaleCreate<'a>]),
   SaleStart(&'a [SaleStart<'a>]),
   SaleUpdate(&'a [SaleUpdate<'a>]),
   SalePause(&'a [SalePause<'a>]),
+  SaleEnd(&'a [SaleEnd<'a>]),
+  AuctionCreate(&'a [AuctionCreate<'a>]),
+  AuctionBid(&'a [AuctionBid<'a>]),
+  AuctionCancel(&'a [AuctionCancel<'a>]),
+  AuctionSettle(&'a [AuctionSettle<'a>]),
+  MarketList(&'a [MarketList<'a>]),
+  MarketRemove(&'a [MarketRemove<'a>]),
+  MarketBuy(&'a [MarketBuy<'a>]),
+  OfferMake(&'a [OfferMake<'a>]),
+  OfferCancel(&'a [OfferCancel<'a>]),
+  OfferAccept(&'a [OfferAccept<'a>]),
+  PackCreate(&'a [PackCreate<'a>]),
+  PackOpen(&'a [PackOpen<'a>]),
+  PackReveal(&'a [PackReveal<'a>]),
+  RecipeUpdate(&'a [RecipeUpdate<'a>]),
+  Craft(&'a [Craft<'a>]),
+  CraftComplete(&'a [CraftComplete<'a>]),
+  VaultCreate(&'a [VaultCreate<'a>]),
+  VaultTransfer(&'a [VaultTransfer<'a>]),
+  VaultBuyout(&'a [VaultBuyout<'a>]),
+  VaultRedeem(&'a [VaultRedeem<'a>]),
+  RoyaltyDefaultUpdate(&'a [RoyaltyDefaultUpdate<'a>]),
+  RoyaltyUpdate(&'a [RoyaltyUpdate<'a>]),
+  RoyaltyPropose(&'a [RoyaltyPropose<'a>]),
+  TransferFeeUpdate(&'a [TransferFeeUpdate<'a>]),
+  TransferRoyalty(&'a [TransferRoyalty<'a>]),
+  SalePhasesUpdate(&'a [SalePhasesUpdate<'a>]),
+  SaleRevenue(&'a [SaleRevenue<'a>]),
+  SaleWithdraw(&'a [SaleWithdraw<'a>]),
+  SaleReserve(&'a [SaleReserve<'a>]),
+  SaleReveal(&'a [SaleReveal<'a>]),
+  SaleRefund(&'a [SaleRefund<'a>]),
+  SaleTemplateAdd(&'a [SaleTemplateAdd<'a>]),
+  SaleDropTableUpdate(&'a [SaleDropTableUpdate<'a>]),
   AccountLock(&'a [AccountLock<'a>]),
   NftUpgrade(&'a [NftUpgrade<'a>]),
+  UpgradePriceUpdate(&'a [UpgradePriceUpdate<'a>]),
 }
 
 fn new_171_mf<'a>(version: &'static str, event_kind: Nep171MfEventKind<'a>) -> NearEvent<'a> {
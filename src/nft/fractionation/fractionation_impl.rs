@@ -1,9 +1,9 @@
 use crate::{NonFungibleToken, TokenId};
-use crate::nft::fractionation::{NonFungibleTokenFractionation, Fractionation};
+use crate::nft::fractionation::{NonFungibleTokenFractionation, NonFungibleTokenFractionationReversal, Fractionation, FractionationReversal, FractionationPart};
 use near_sdk::json_types::U128;
 use near_sdk::{require, env};
-use crate::nft::date_now;
-use crate::nft::events_171_mf::FractionationComplete;
+use crate::nft::{date_now, refund_approved_account_ids, refund_deposit, refund_deposit_to_account_with_price, storage_used_since, transfer_near_or_ft};
+use crate::nft::events_171_mf::{FractionationComplete, FractionationReversalUpdate, FractionationSplit};
 
 impl NonFungibleTokenFractionation for NonFungibleToken {
   fn nft_fractionation(&self, token_id: TokenId) -> Fractionation {
@@ -39,6 +39,7 @@ impl NonFungibleTokenFractionation for NonFungibleToken {
   }
 
   fn nft_fractionation_complete(&mut self, token_id: TokenId) {
+    let initial_storage_usage = env::storage_usage();
     let from = env::current_account_id();
 
     self.assert_available_transfer(&token_id, &from);
@@ -52,6 +53,12 @@ impl NonFungibleTokenFractionation for NonFungibleToken {
 
     let sender_id = env::predecessor_account_id();
 
+    // parts are minted again from the snapshot when the token is split
+    let parts: Vec<FractionationPart> = fractionation.iter()
+      .map(|token_id| self.internal_fractionation_part(&token_id))
+      .collect();
+    self.fractionation_parts_by_id.insert(&token_id, &parts);
+
     // burn items
     fractionation.iter()
       .for_each(|token_id| {
@@ -72,5 +79,96 @@ impl NonFungibleTokenFractionation for NonFungibleToken {
       to: &sender_id,
       completed_at: &date
     }.emit();
+
+    // burned parts usually release more than is used, refund the attached deposit
+    refund_deposit(storage_used_since(initial_storage_usage));
+  }
+}
+
+impl NonFungibleTokenFractionationReversal for NonFungibleToken {
+  fn nft_fractionation_reversal_set(&mut self, token_id: TokenId, reversal: Option<FractionationReversal>) {
+    self.assert_owner();
+
+    assert!(self.fractionation_by_id.as_ref().unwrap().contains_key(&token_id), "Not found fractionation");
+    if self.fractionation_completed_by_id.as_ref().unwrap().contains_key(&token_id) {
+      // completed before parts were kept, it cannot be split back
+      assert!(self.fractionation_parts_by_id.get(&token_id).is_some(), "Not found fractionation parts");
+    }
+
+    match &reversal {
+      Some(reversal) => self.fractionation_reversal_by_id.insert(&token_id, reversal),
+      None => self.fractionation_reversal_by_id.remove(&token_id),
+    };
+
+    FractionationReversalUpdate {
+      token_id: &token_id,
+      reversal: reversal.as_ref(),
+    }.emit();
+  }
+
+  fn nft_fractionation_reversal(&self, token_id: TokenId) -> Option<FractionationReversal> {
+    self.fractionation_reversal_by_id.get(&token_id)
+  }
+
+  fn nft_fractionation_split(&mut self, token_id: TokenId) -> Vec<TokenId> {
+    let initial_storage_usage = env::storage_usage();
+    let sender_id = env::predecessor_account_id();
+    let contract_id = env::current_account_id();
+
+    let reversal = self.fractionation_reversal_by_id.get(&token_id).expect("Fractionation reversal is disabled");
+    let completed_at = self.fractionation_completed_by_id.as_ref().unwrap().get(&token_id).expect("Fractionation is not completed");
+    assert!(date_now() >= completed_at + reversal.cooldown, "Fractionation reversal is on cooldown");
+
+    let owner_id = self.owner_by_id.get(&token_id).expect("Token not found");
+    assert_eq!(owner_id, sender_id, "Only owner can split the token");
+
+    let parts = self.fractionation_parts_by_id.get(&token_id).expect("Not found fractionation parts");
+    assert!(env::attached_deposit() >= reversal.fee.0, "Deposit is less than the split fee");
+
+    // escrow the token, it is handed over again by the next completion
+    let (_, approved_account_ids) = self.internal_transfer(&sender_id, &contract_id, &token_id, None, None);
+
+    if let Some(approved_account_ids) = approved_account_ids {
+      refund_approved_account_ids(sender_id.clone(), &approved_account_ids);
+    }
+
+    self.fractionation_completed_by_id.as_mut().unwrap().remove(&token_id);
+    self.fractionation_parts_by_id.remove(&token_id);
+    self.fractionation_ids.as_mut().unwrap().insert(&token_id);
+
+    let token_ids: Vec<TokenId> = parts.into_iter()
+      .map(|part| {
+        self.internal_create_nft_with_refund(
+          &part.token_id,
+          Some(sender_id.clone()),
+          part.metadata,
+          part.rarity,
+          part.collection,
+          Some(part.bind_to_owner),
+          None,
+          part.royalty,
+          Some(token_id.clone()),
+          part.token_type,
+          part.token_sub_type,
+          None,
+        );
+
+        part.token_id
+      })
+      .collect();
+
+    FractionationSplit {
+      token_id: &token_id,
+      owner_id: &sender_id,
+      token_ids: &token_ids,
+    }.emit();
+
+    refund_deposit_to_account_with_price(storage_used_since(initial_storage_usage), reversal.fee.0, sender_id);
+
+    if reversal.fee.0 > 0 {
+      transfer_near_or_ft(&None, &self.owner_id, reversal.fee.0, format!("Fractionation {} split fee", token_id));
+    }
+
+    token_ids
   }
 }
@@ -1,4 +1,5 @@
 use crate::nft::{NonFungibleToken, TokenId, Fractionation};
+use crate::nft::fractionation::FractionationPart;
 use near_sdk::collections::UnorderedSet;
 use near_sdk::borsh::{self, BorshSerialize};
 use near_sdk::{BorshStorageKey, env};
@@ -21,6 +22,7 @@ impl NonFungibleToken {
   pub fn enum_fractionation(&self, token_id: &TokenId) -> Fractionation {
     let entries = self.fractionation_by_id.as_ref().unwrap().get(&token_id).expect("Not found fractionation");
     let completed_at = self.fractionation_completed_by_id.as_ref().unwrap().get(&token_id);
+    let reversal = self.fractionation_reversal_by_id.get(token_id);
 
     let collection = self.token_collection_by_id.as_ref().unwrap().get(&token_id);
     let token_type = self.token_type_by_id.as_ref().unwrap().get(&token_id);
@@ -31,6 +33,7 @@ impl NonFungibleToken {
       token_id: token_id.clone(),
       entries: entries.to_vec(),
       completed_at,
+      reversal,
       metadata,
       collection,
       token_type,
@@ -38,6 +41,19 @@ impl NonFungibleToken {
     }
   }
 
+  pub(crate) fn internal_fractionation_part(&self, token_id: &TokenId) -> FractionationPart {
+    FractionationPart {
+      token_id: token_id.clone(),
+      metadata: self.token_metadata_by_id.as_ref().unwrap().get(token_id),
+      collection: self.token_collection_by_id.as_ref().unwrap().get(token_id).expect("Not found collection"),
+      token_type: self.token_type_by_id.as_ref().unwrap().get(token_id).expect("Not found token type"),
+      token_sub_type: self.token_sub_type_by_id.as_ref().unwrap().get(token_id),
+      rarity: self.token_rarity_by_id.as_ref().unwrap().get(token_id).expect("Not found rarity"),
+      royalty: self.royalty.token_royalty_by_id.get(token_id),
+      bind_to_owner: self.bind_to_owner.internal_is_bind_to_owner(token_id),
+    }
+  }
+
   pub fn internal_remove_fractionation(&mut self, token_id: &TokenId) {
     self.fractionation_ids.as_mut().unwrap().remove(&token_id);
   }
@@ -1,8 +1,10 @@
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
 
-use crate::nft::{TokenId, TokenCollection, TokenRarity, TokenType};
+use crate::nft::{TokenId, TokenCollection, TokenRarity, TokenType, TokenSubType};
 use crate::nft::metadata::TokenMetadata;
+use crate::nft::royalty::Royalty;
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
@@ -10,6 +12,7 @@ pub struct Fractionation {
   pub token_id: TokenId,
   pub entries: Vec<TokenId>,
   pub completed_at: Option<u64>,
+  pub reversal: Option<FractionationReversal>,
 
   // custom
 
@@ -18,3 +21,25 @@ pub struct Fractionation {
   pub token_type: Option<TokenType>,
   pub rarity: Option<TokenRarity>,
 }
+
+/// Split of the completed token back into parts, `cooldown` is in milliseconds since the completion
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FractionationReversal {
+  pub cooldown: u64,
+  pub fee: U128,
+}
+
+/// Part of the fractionation as it was burned on completion
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FractionationPart {
+  pub token_id: TokenId,
+  pub metadata: Option<TokenMetadata>,
+  pub collection: TokenCollection,
+  pub token_type: TokenType,
+  pub token_sub_type: Option<TokenSubType>,
+  pub rarity: TokenRarity,
+  pub royalty: Option<Royalty>,
+  pub bind_to_owner: bool,
+}
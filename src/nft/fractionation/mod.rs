@@ -13,3 +13,14 @@ pub trait NonFungibleTokenFractionation {
   fn nft_fractionations_supply(&self) -> U128;
   fn nft_fractionation_complete(&mut self, token_id: TokenId);
 }
+
+/// Holder of the completed token can split it back into parts when the reversal is set
+pub trait NonFungibleTokenFractionationReversal {
+  /// `None` disables the reversal
+  fn nft_fractionation_reversal_set(&mut self, token_id: TokenId, reversal: Option<FractionationReversal>);
+
+  fn nft_fractionation_reversal(&self, token_id: TokenId) -> Option<FractionationReversal>;
+
+  /// Escrow the completed token and mint its parts to the holder, returns ids of the parts
+  fn nft_fractionation_split(&mut self, token_id: TokenId) -> Vec<TokenId>;
+}
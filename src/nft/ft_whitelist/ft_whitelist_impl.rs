@@ -0,0 +1,79 @@
+use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::{env, AccountId, BorshStorageKey, IntoStorageKey};
+use crate::nft::ft_whitelist::{FtWhitelist, FtPurpose};
+
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum StorageKey {
+  FtWhitelistInner { purpose_hash: Vec<u8> },
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct FtWhitelistFeature {
+  pub ft_tokens_by_purpose: LookupMap<FtPurpose, UnorderedSet<AccountId>>,
+}
+
+impl FtWhitelistFeature {
+  pub fn new<Q>(prefix: Q) -> Self where Q: IntoStorageKey {
+    Self {
+      ft_tokens_by_purpose: LookupMap::new(prefix),
+    }
+  }
+
+  pub(crate) fn assert_whitelisted(&self, purpose: &FtPurpose, ft_token_id: &AccountId) {
+    if !self.internal_is_whitelisted(purpose, ft_token_id) {
+      env::panic_str(&format!("Unavailable ft {} for {:?}", ft_token_id, purpose));
+    }
+  }
+
+  pub(crate) fn internal_is_whitelisted(&self, purpose: &FtPurpose, ft_token_id: &AccountId) -> bool {
+    self.ft_tokens_by_purpose
+      .get(purpose)
+      .map(|ft_tokens| ft_tokens.contains(ft_token_id))
+      .unwrap_or(false)
+  }
+
+  pub(crate) fn internal_whitelist_add(&mut self, purpose: &FtPurpose, ft_token_id: &AccountId) -> bool {
+    let mut ft_tokens = self.ft_tokens_by_purpose.get(purpose).unwrap_or_else(|| {
+      UnorderedSet::new(StorageKey::FtWhitelistInner {
+        purpose_hash: env::sha256(&purpose.try_to_vec().unwrap()),
+      })
+    });
+    let added = ft_tokens.insert(ft_token_id);
+    self.ft_tokens_by_purpose.insert(purpose, &ft_tokens);
+
+    added
+  }
+
+  pub(crate) fn internal_whitelist_remove(&mut self, purpose: &FtPurpose, ft_token_id: &AccountId) -> bool {
+    if let Some(mut ft_tokens) = self.ft_tokens_by_purpose.get(purpose) {
+      let removed = ft_tokens.remove(ft_token_id);
+      self.ft_tokens_by_purpose.insert(purpose, &ft_tokens);
+
+      return removed;
+    }
+
+    false
+  }
+}
+
+impl FtWhitelist for FtWhitelistFeature {
+  fn nft_ft_whitelist_add(&mut self, purpose: FtPurpose, ft_token_id: AccountId) -> bool {
+    self.internal_whitelist_add(&purpose, &ft_token_id)
+  }
+
+  fn nft_ft_whitelist_remove(&mut self, purpose: FtPurpose, ft_token_id: AccountId) -> bool {
+    self.internal_whitelist_remove(&purpose, &ft_token_id)
+  }
+
+  fn nft_ft_whitelist(&self, purpose: FtPurpose) -> Vec<AccountId> {
+    self.ft_tokens_by_purpose
+      .get(&purpose)
+      .map(|ft_tokens| ft_tokens.to_vec())
+      .unwrap_or_default()
+  }
+
+  fn nft_ft_is_whitelisted(&self, purpose: FtPurpose, ft_token_id: AccountId) -> bool {
+    self.internal_is_whitelisted(&purpose, &ft_token_id)
+  }
+}
@@ -0,0 +1,29 @@
+// Ft whitelist
+
+#[macro_export]
+macro_rules! impl_ft_whitelist_feature {
+    ($contract: ident, $instance: ident, $assert_owner: ident) => {
+        use $crate::ft_whitelist::{FtWhitelist, FtPurpose};
+
+        #[near_bindgen]
+        impl FtWhitelist for $contract {
+          fn nft_ft_whitelist_add(&mut self, purpose: FtPurpose, ft_token_id: AccountId) -> bool {
+            self.$assert_owner();
+            self.$instance.ft_whitelist.nft_ft_whitelist_add(purpose, ft_token_id)
+          }
+
+          fn nft_ft_whitelist_remove(&mut self, purpose: FtPurpose, ft_token_id: AccountId) -> bool {
+            self.$assert_owner();
+            self.$instance.ft_whitelist.nft_ft_whitelist_remove(purpose, ft_token_id)
+          }
+
+          fn nft_ft_whitelist(&self, purpose: FtPurpose) -> Vec<AccountId> {
+            self.$instance.ft_whitelist.nft_ft_whitelist(purpose)
+          }
+
+          fn nft_ft_is_whitelisted(&self, purpose: FtPurpose, ft_token_id: AccountId) -> bool {
+            self.$instance.ft_whitelist.nft_ft_is_whitelisted(purpose, ft_token_id)
+          }
+        }
+    };
+}
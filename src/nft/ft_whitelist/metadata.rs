@@ -0,0 +1,15 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// What a whitelisted fungible token may be used for. Add new variants at the end only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum FtPurpose {
+  Upgrade,
+  SaleMint,
+  Auction,
+  Market,
+  Offer,
+  Craft,
+  Vault,
+}
@@ -0,0 +1,16 @@
+pub use ft_whitelist_impl::*;
+pub use metadata::*;
+use near_sdk::AccountId;
+
+pub mod ft_whitelist_impl;
+pub mod metadata;
+mod macros;
+
+/// Registry of fungible tokens the contract accepts in `ft_on_transfer`, per purpose
+pub trait FtWhitelist {
+  fn nft_ft_whitelist_add(&mut self, purpose: FtPurpose, ft_token_id: AccountId) -> bool;
+  fn nft_ft_whitelist_remove(&mut self, purpose: FtPurpose, ft_token_id: AccountId) -> bool;
+
+  fn nft_ft_whitelist(&self, purpose: FtPurpose) -> Vec<AccountId>;
+  fn nft_ft_is_whitelisted(&self, purpose: FtPurpose, ft_token_id: AccountId) -> bool;
+}
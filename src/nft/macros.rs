@@ -3,7 +3,8 @@
 #[macro_export]
 macro_rules! impl_non_fungible_token_fractionation {
     ($contract: ident, $tokens: ident) => {
-        use $crate::{NonFungibleTokenFractionation, Fractionation};
+        use $crate::{NonFungibleTokenFractionation, NonFungibleTokenFractionationReversal, Fractionation};
+        use $crate::nft::fractionation::FractionationReversal;
 
         #[near_bindgen]
         impl NonFungibleTokenFractionation for $contract {
@@ -16,10 +17,25 @@ macro_rules! impl_non_fungible_token_fractionation {
           fn nft_fractionations_supply(&self) -> U128 {
             self.$tokens.nft_fractionations_supply()
           }
+          #[payable]
           fn nft_fractionation_complete(&mut self, token_id: TokenId) {
             self.$tokens.nft_fractionation_complete(token_id)
           }
         }
+
+        #[near_bindgen]
+        impl NonFungibleTokenFractionationReversal for $contract {
+          fn nft_fractionation_reversal_set(&mut self, token_id: TokenId, reversal: Option<FractionationReversal>) {
+            self.$tokens.nft_fractionation_reversal_set(token_id, reversal)
+          }
+          fn nft_fractionation_reversal(&self, token_id: TokenId) -> Option<FractionationReversal> {
+            self.$tokens.nft_fractionation_reversal(token_id)
+          }
+          #[payable]
+          fn nft_fractionation_split(&mut self, token_id: TokenId) -> Vec<TokenId> {
+            self.$tokens.nft_fractionation_split(token_id)
+          }
+        }
     };
 }
 
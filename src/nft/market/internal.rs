@@ -0,0 +1,117 @@
+use near_sdk::{AccountId, env, Balance, BorshStorageKey};
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use crate::nft::{NonFungibleToken, TokenId, TokenCollection, refund_approved_account_ids};
+use crate::nft::market::Listing;
+use crate::nft::events_171_mf::{MarketRemove, MarketBuy};
+
+/// Hashed values are tagged, other modules use the same variant index for keys hashed by account
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum StorageKey {
+  ListingsPerOwnerInner { account_hash: Vec<u8> },
+  ListingsPerCollectionInner { collection_hash: Vec<u8> },
+}
+
+impl NonFungibleToken {
+  pub(crate) fn assert_token_not_listed(&self, token_id: &TokenId) {
+    if self.listing_by_token.get(token_id).is_some() {
+      env::panic_str("Token is listed on market");
+    }
+  }
+
+  pub(crate) fn internal_market_insert(&mut self, listing: &Listing) {
+    let mut owner_listings = self.listings_per_owner.get(&listing.owner_id).unwrap_or_else(|| {
+      UnorderedSet::new(StorageKey::ListingsPerOwnerInner {
+        account_hash: env::sha256(format!("listings:{}", listing.owner_id).as_bytes()),
+      })
+    });
+    owner_listings.insert(&listing.token_id);
+    self.listings_per_owner.insert(&listing.owner_id, &owner_listings);
+
+    if let Some(collection) = self.token_collection_by_id.as_ref().unwrap().get(&listing.token_id) {
+      let mut collection_listings = self.listings_per_collection.get(&collection).unwrap_or_else(|| {
+        UnorderedSet::new(StorageKey::ListingsPerCollectionInner {
+          collection_hash: env::sha256(&collection.try_to_vec().unwrap()),
+        })
+      });
+      collection_listings.insert(&listing.token_id);
+      self.listings_per_collection.insert(&collection, &collection_listings);
+    }
+
+    self.listings_by_price.insert(&(listing.ft_token.clone(), listing.price.0, listing.token_id.clone()), &());
+    self.listing_by_token.insert(&listing.token_id, listing);
+  }
+
+  /// Remove the listing of the token if there is one, called on every transfer and burn
+  pub(crate) fn internal_market_remove(&mut self, token_id: &TokenId) -> Option<Listing> {
+    let listing = self.listing_by_token.remove(token_id)?;
+
+    if let Some(mut owner_listings) = self.listings_per_owner.get(&listing.owner_id) {
+      owner_listings.remove(token_id);
+
+      if owner_listings.is_empty() {
+        self.listings_per_owner.remove(&listing.owner_id);
+      } else {
+        self.listings_per_owner.insert(&listing.owner_id, &owner_listings);
+      }
+    }
+
+    let collection: Option<TokenCollection> = self.token_collection_by_id.as_ref().unwrap().get(token_id);
+    if let Some(collection) = collection {
+      if let Some(mut collection_listings) = self.listings_per_collection.get(&collection) {
+        collection_listings.remove(token_id);
+
+        if collection_listings.is_empty() {
+          self.listings_per_collection.remove(&collection);
+        } else {
+          self.listings_per_collection.insert(&collection, &collection_listings);
+        }
+      }
+    }
+
+    self.listings_by_price.remove(&(listing.ft_token.clone(), listing.price.0, token_id.clone()));
+
+    MarketRemove {
+      token_id,
+      owner_id: &listing.owner_id,
+    }.emit();
+
+    Some(listing)
+  }
+
+  /// Transfer a listed token paid with `amount` to `receiver_id` and pay the seller and royalties
+  pub(crate) fn internal_market_buy(&mut self, token_id: &TokenId, buyer_id: &AccountId, receiver_id: &AccountId, amount: Balance, ft_token: Option<AccountId>) -> Listing {
+    let listing = self.listing_by_token.get(token_id).expect("Not found listing");
+
+    assert_eq!(listing.ft_token, ft_token, "Invalid listing currency");
+    assert!(amount >= listing.price.0, "Invalid attached price");
+    assert_ne!(&listing.owner_id, receiver_id, "Current and next owner must differ");
+    self.assert_transfer_fee_price(token_id, &listing.owner_id, receiver_id, listing.price.0);
+
+    self.internal_transfer_unguarded(token_id, &listing.owner_id, receiver_id);
+
+    if let Some(approved_account_ids) = self.approvals_by_id.as_mut().and_then(|by_id| by_id.remove(token_id)) {
+      refund_approved_account_ids(listing.owner_id.clone(), &approved_account_ids);
+    }
+
+    let payout = self.internal_payout(token_id, &listing.owner_id, listing.price.0, None);
+
+    payout
+      .iter()
+      .filter(|(_, amount)| amount.0 > 0)
+      .for_each(|(account_id, amount)| {
+        self.internal_payout_transfer(&listing.ft_token, account_id, amount.0, format!("Market {} sale", token_id));
+      });
+
+    MarketBuy {
+      token_id,
+      owner_id: &listing.owner_id,
+      buyer_id,
+      receiver_id,
+      ft_token: listing.ft_token.as_ref(),
+      price: &listing.price,
+    }.emit();
+
+    listing
+  }
+}
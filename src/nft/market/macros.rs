@@ -0,0 +1,53 @@
+// Market
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_market {
+    ($contract: ident, $tokens: ident $(, $assert_transfer: ident)?) => {
+        use $crate::nft::market::{NonFungibleTokenMarket, NonFungibleTokenMarketEnumeration, Listing};
+
+        #[near_bindgen]
+        impl NonFungibleTokenMarket for $contract {
+          #[payable]
+          fn nft_market_list(&mut self, token_id: TokenId, ft_token: Option<AccountId>, price: U128) -> Listing {
+            $(self.$assert_transfer(&token_id, &env::current_account_id());)?
+
+            self.$tokens.nft_market_list(token_id, ft_token, price)
+          }
+
+          #[payable]
+          fn nft_market_unlist(&mut self, token_id: TokenId) {
+            self.$tokens.nft_market_unlist(token_id)
+          }
+
+          #[payable]
+          fn nft_market_buy(&mut self, token_id: TokenId, receiver_id: Option<AccountId>) -> Listing {
+            $(self.$assert_transfer(&token_id, &env::predecessor_account_id());)?
+
+            self.$tokens.nft_market_buy(token_id, receiver_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenMarketEnumeration for $contract {
+          fn nft_market_listing(&self, token_id: TokenId) -> Option<Listing> {
+            self.$tokens.nft_market_listing(token_id)
+          }
+
+          fn nft_market_listings(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+            self.$tokens.nft_market_listings(from_index, limit)
+          }
+
+          fn nft_market_listings_by_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+            self.$tokens.nft_market_listings_by_owner(account_id, from_index, limit)
+          }
+
+          fn nft_market_listings_by_collection(&self, collection: TokenCollection, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+            self.$tokens.nft_market_listings_by_collection(collection, from_index, limit)
+          }
+
+          fn nft_market_listings_by_price(&self, ft_token: Option<AccountId>, from_price: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+            self.$tokens.nft_market_listings_by_price(ft_token, from_price, limit)
+          }
+        }
+    };
+}
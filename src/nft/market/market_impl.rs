@@ -0,0 +1,204 @@
+use near_sdk::{env, require, assert_one_yocto, AccountId};
+use near_sdk::json_types::U128;
+use std::ops::Bound;
+use crate::nft::{NonFungibleToken, TokenId, TokenCollection, storage_used_since};
+use crate::nft::market::{NonFungibleTokenMarket, NonFungibleTokenMarketEnumeration, Listing};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::events_171_mf::MarketList;
+
+impl NonFungibleTokenMarket for NonFungibleToken {
+  fn nft_market_list(&mut self, token_id: TokenId, ft_token: Option<AccountId>, price: U128) -> Listing {
+    let owner_id = self.assert_token_holder(&token_id);
+    let initial_storage_usage = env::storage_usage();
+
+    self.assert_available_transfer(&token_id, &owner_id);
+    assert!(price.0 > 0, "Price must be greater than 0");
+
+    if let Some(ft_token) = &ft_token {
+      self.ft_whitelist.assert_whitelisted(&FtPurpose::Market, ft_token);
+    }
+
+    // relisting replaces the previous price
+    self.internal_market_remove(&token_id);
+
+    let listing = Listing {
+      token_id: token_id.clone(),
+      owner_id: owner_id.clone(),
+      ft_token,
+      price,
+      created_at: env::block_timestamp(),
+    };
+
+    self.internal_market_insert(&listing);
+
+    MarketList {
+      listing: &listing,
+    }.emit();
+
+    self.internal_storage_charge(&owner_id, storage_used_since(initial_storage_usage), 0);
+
+    listing
+  }
+
+  fn nft_market_unlist(&mut self, token_id: TokenId) {
+    assert_one_yocto();
+    self.assert_token_holder(&token_id);
+
+    self.internal_market_remove(&token_id).expect("Not found listing");
+  }
+
+  fn nft_market_buy(&mut self, token_id: TokenId, receiver_id: Option<AccountId>) -> Listing {
+    let buyer_id = env::predecessor_account_id();
+    let receiver_id = receiver_id.unwrap_or_else(|| buyer_id.clone());
+    let initial_storage_usage = env::storage_usage();
+
+    let listing = self.internal_market_buy(&token_id, &buyer_id, &receiver_id, env::attached_deposit(), None);
+
+    self.internal_storage_charge(&buyer_id, storage_used_since(initial_storage_usage), listing.price.0);
+
+    listing
+  }
+}
+
+impl NonFungibleTokenMarketEnumeration for NonFungibleToken {
+  fn nft_market_listing(&self, token_id: TokenId) -> Option<Listing> {
+    self.listing_by_token.get(&token_id)
+  }
+
+  fn nft_market_listings(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.listing_by_token.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.listing_by_token
+      .values()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+
+  fn nft_market_listings_by_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+    let token_set = if let Some(token_set) = self.listings_per_owner.get(&account_id) {
+      token_set
+    } else {
+      return vec![];
+    };
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    token_set
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .map(|token_id| self.listing_by_token.get(&token_id).unwrap())
+      .collect()
+  }
+
+  fn nft_market_listings_by_collection(&self, collection: TokenCollection, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+    let token_set = if let Some(token_set) = self.listings_per_collection.get(&collection) {
+      token_set
+    } else {
+      return vec![];
+    };
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    token_set
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .map(|token_id| self.listing_by_token.get(&token_id).unwrap())
+      .collect()
+  }
+
+  fn nft_market_listings_by_price(&self, ft_token: Option<AccountId>, from_price: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+    let from_key = (ft_token.clone(), from_price.map(|price| price.0).unwrap_or(0), String::new());
+
+    self.listings_by_price
+      .range((Bound::Included(from_key), Bound::Unbounded))
+      .take_while(|((listing_ft_token, _, _), _)| listing_ft_token == &ft_token)
+      .take(limit)
+      .map(|((_, _, token_id), _)| self.listing_by_token.get(&token_id).unwrap())
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::nft::test_utils::*;
+
+  fn listing(tokens: &mut NonFungibleToken, seller_id: &AccountId, price: u128) -> TokenId {
+    mint(tokens, "token", Some(seller_id), None);
+
+    set_context(&context(seller_id, NEAR));
+    tokens.nft_market_list("token".to_string(), None, U128::from(price));
+
+    "token".to_string()
+  }
+
+  #[test]
+  fn buy_transfers_token_and_pays_seller_and_royalties() {
+    let mut tokens = new_tokens();
+    let (seller, buyer, artist) = (account("seller"), account("buyer"), account("artist"));
+    let token_id = listing(&mut tokens, &seller, 10 * NEAR);
+    tokens.royalty.token_royalty_by_id.insert(&token_id, &vec![(artist.clone(), 1_000)].into_iter().collect());
+
+    set_context(&context(&buyer, 11 * NEAR));
+    tokens.nft_market_buy(token_id.clone(), None);
+
+    let paid = transfers();
+    assert!(paid.contains(&(seller.clone(), 9 * NEAR)));
+    assert!(paid.contains(&(artist, NEAR)));
+    assert_eq!(tokens.owner_by_id.get(&token_id), Some(buyer));
+    assert!(tokens.nft_market_listing(token_id).is_none());
+    assert!(tokens.nft_market_listings_by_owner(seller, None, None).is_empty());
+  }
+
+  #[test]
+  #[should_panic(expected = "Invalid attached price")]
+  fn buy_below_price_fails() {
+    let mut tokens = new_tokens();
+    let token_id = listing(&mut tokens, &account("seller"), 10 * NEAR);
+
+    set_context(&context(&account("buyer"), 10 * NEAR - 1));
+    tokens.nft_market_buy(token_id, None);
+  }
+
+  #[test]
+  #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+  fn unlist_requires_one_yocto() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = listing(&mut tokens, &seller, 10 * NEAR);
+
+    set_context(&context(&seller, 0));
+    tokens.nft_market_unlist(token_id);
+  }
+
+  #[test]
+  fn relisting_replaces_the_price() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = listing(&mut tokens, &seller, 10 * NEAR);
+
+    set_context(&context(&seller, NEAR));
+    tokens.nft_market_list(token_id.clone(), None, U128::from(5 * NEAR));
+
+    let by_price = tokens.nft_market_listings_by_price(None, None, None);
+    assert_eq!(by_price.len(), 1);
+    assert_eq!(by_price[0].price.0, 5 * NEAR);
+    assert_eq!(tokens.nft_market_listings_by_owner(seller, None, None).len(), 1);
+  }
+}
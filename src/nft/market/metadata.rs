@@ -0,0 +1,16 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use crate::nft::TokenId;
+
+/// `ft_token` is `None` for listings in NEAR
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Listing {
+  pub token_id: TokenId,
+  pub owner_id: AccountId,
+  pub ft_token: Option<AccountId>,
+  pub price: U128,
+  pub created_at: u64,
+}
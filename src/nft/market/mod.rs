@@ -0,0 +1,33 @@
+pub use metadata::*;
+use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::{TokenId, TokenCollection};
+
+pub mod metadata;
+pub mod market_impl;
+mod internal;
+mod macros;
+
+/// Fixed price listings, a listing is removed when the token is transferred, burned or bound to its owner
+pub trait NonFungibleTokenMarket {
+  /// List the token of the caller for `price` in NEAR or in `ft_token`
+  fn nft_market_list(&mut self, token_id: TokenId, ft_token: Option<AccountId>, price: U128) -> Listing;
+
+  fn nft_market_unlist(&mut self, token_id: TokenId);
+
+  /// Buy a token listed in NEAR, the rest of the attached deposit is refunded
+  fn nft_market_buy(&mut self, token_id: TokenId, receiver_id: Option<AccountId>) -> Listing;
+}
+
+pub trait NonFungibleTokenMarketEnumeration {
+  fn nft_market_listing(&self, token_id: TokenId) -> Option<Listing>;
+
+  fn nft_market_listings(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing>;
+
+  fn nft_market_listings_by_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing>;
+
+  fn nft_market_listings_by_collection(&self, collection: TokenCollection, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing>;
+
+  /// Listings in `ft_token` (`None` for NEAR) from the cheapest, starting at `from_price`
+  fn nft_market_listings_by_price(&self, ft_token: Option<AccountId>, from_price: Option<U128>, limit: Option<u64>) -> Vec<Listing>;
+}
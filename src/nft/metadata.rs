@@ -37,6 +37,35 @@ pub struct TokenMetadata {
   pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
 }
 
+/// NEP-330 source metadata, `standards` lists the NEPs the contract implements.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSourceMetadata {
+  pub version: Option<String>,
+  pub link: Option<String>,
+  pub standards: Vec<Standard>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Standard {
+  pub standard: String,
+  pub version: String,
+}
+
+impl Standard {
+  pub fn new(standard: &str, version: &str) -> Self {
+    Self {
+      standard: standard.to_string(),
+      version: version.to_string(),
+    }
+  }
+}
+
+pub trait ContractSourceMetadataProvider {
+  fn contract_source_metadata(&self) -> ContractSourceMetadata;
+}
+
 /// Offers details on the contract-level metadata.
 pub trait NonFungibleTokenMetadataProvider {
   fn nft_metadata(&self) -> NFTContractMetadata;
@@ -1,4 +1,4 @@
-use crate::nft::{NonFungibleToken, TokenRarity, TokenCollection, SaleId, Royalty, TokenId, TokenType, Token};
+use crate::nft::{NonFungibleToken, TokenRarity, TokenCollection, SaleId, Royalty, TokenId, TokenType, Token, refund_deposit_to_account, storage_used_since};
 use crate::nft::metadata::{TokenMetadata, TokenSubType};
 use near_sdk::{AccountId, env};
 use near_sdk::collections::UnorderedSet;
@@ -60,11 +60,10 @@ impl NonFungibleToken {
     fractionation_id: Option<TokenId>,
     token_type: TokenType,
     token_sub_type: Option<TokenSubType>,
-    _refund_id: Option<AccountId>,
+    refund_id: Option<AccountId>,
   ) -> Token {
-    // let prev_storage = env::storage_usage();
     // Remember current storage usage if refund_id is Some
-    // let _initial_storage_usage = refund_id.map(|account_id| (account_id, env::storage_usage()));
+    let initial_storage_usage = refund_id.map(|account_id| (account_id, env::storage_usage()));
 
     // assert!(sale_id.is_some(), "Require sale id");
 
@@ -119,13 +118,18 @@ impl NonFungibleToken {
     }
 
     if let Some(bind_to_owner) = &bind_to_owner {
-      self.bind_to_owner.internal_token_bind_to_owner(&token_id, &bind_to_owner);
+      self.internal_token_bind_to_owner(token_id, bind_to_owner);
     }
     if let Some(sale_id) = &sale_id {
       self.internal_sale_add_token(&sale_id, &token_id);
     }
     let royalty = self.royalty.internal_royalty_calculate(perpetual_royalties);
 
+    // tokens without own royalties use the defaults of their collection or type
+    if !royalty.is_empty() {
+      self.royalty.token_royalty_by_id.insert(token_id, &royalty);
+    }
+
     // Enumeration extension: Record tokens_per_owner for use with enumeration view methods.
     if let Some(tokens_per_owner) = &mut self.tokens_per_owner {
       let mut token_ids = tokens_per_owner.get(&owner_id).unwrap_or_else(|| {
@@ -141,11 +145,6 @@ impl NonFungibleToken {
     let approved_account_ids =
       if self.approvals_by_id.is_some() { Some(HashMap::new()) } else { None };
 
-    // if let Some((id, storage_usage)) = initial_storage_usage {
-    // refund_deposit_to_account(env::storage_usage() - storage_usage, id)
-    // }
-    // Return any extra attached deposit not used for storage
-
     let token = Token {
       token_id: token_id.clone(),
       owner_id: owner_id.clone(),
@@ -180,7 +179,10 @@ impl NonFungibleToken {
       }
     }
 
-    // env::log_str(&format!("{}{}{}", &prev_storage.to_string(), ", ".to_string(), &env::storage_usage().to_string()));
+    // Return any extra attached deposit not used for storage
+    if let Some((id, storage_usage)) = initial_storage_usage {
+      refund_deposit_to_account(storage_used_since(storage_usage), id)
+    }
 
     token
   }
@@ -7,6 +7,7 @@ macro_rules! impl_non_fungible_token_mint {
 
         #[near_bindgen]
         impl NonFungibleTokenMint for $contract {
+          #[payable]
           fn nft_mint(
             &mut self,
             token_id: TokenId,
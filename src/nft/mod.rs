@@ -1,7 +1,7 @@
 pub use macros::*;
 pub use utils::*;
 
-pub use self::base::{NonFungibleToken};
+pub use self::base::{NonFungibleToken, NonFungibleTokenPrefixes};
 pub use self::token::{Token, TokenId};
 pub use self::fractionation::{Fractionation};
 pub use self::sale::{Sale, JsonSale, SaleId};
@@ -15,6 +15,8 @@ pub mod metadata;
 mod macros;
 mod token;
 mod utils;
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 pub mod approval;
 pub use self::approval::{NonFungibleTokenApproval, NonFungibleTokenApprovalReceiver};
@@ -36,17 +38,39 @@ pub mod royalty;
 pub mod bind_to_owner;
 
 pub mod fractionation;
-pub use self::fractionation::NonFungibleTokenFractionation;
+pub use self::fractionation::{NonFungibleTokenFractionation, NonFungibleTokenFractionationReversal};
 
 pub mod burn;
 pub use self::burn::NonFungibleTokenBurn;
 
 pub mod upgradable;
-pub use self::upgradable::NonFungibleTokenUpgradable;
+pub use self::upgradable::{NonFungibleTokenUpgradable, NonFungibleTokenUpgradePrice, UpgradePrice};
 
 pub mod mint;
 pub use self::mint::NonFungibleTokenMint;
 
+pub mod deposit;
+pub use self::deposit::NonFungibleTokenDeposit;
+
+pub mod ft_whitelist;
+
+pub mod auction;
+
+pub mod market;
+
+pub mod offer;
+
+pub mod pack;
+
+pub mod craft;
+
+pub mod vault;
+
+pub mod transfer_fee;
+
+pub mod storage_management;
+pub use self::storage_management::{StorageManagement, StorageBalance, StorageBalanceBounds};
+
 // pub mod pause;
 // pub use self::pause::ContractPause;
 
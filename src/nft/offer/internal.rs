@@ -0,0 +1,127 @@
+use near_sdk::{AccountId, env, Balance, BorshStorageKey};
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, storage_used_since};
+use crate::nft::offer::{Offer, OfferId, OfferTarget};
+use crate::nft::events_171_mf::OfferMake;
+
+/// Hashed values are tagged, other modules use the same variant index for keys hashed by account
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum StorageKey {
+  OffersPerTargetInner { target_hash: Vec<u8> },
+  OffersPerBuyerInner { account_hash: Vec<u8> },
+}
+
+impl NonFungibleToken {
+  pub(crate) fn assert_offer_target(&self, target: &OfferTarget, token_id: &TokenId) {
+    let is_target = match target {
+      OfferTarget::Token { token_id: offer_token_id } => offer_token_id == token_id,
+      OfferTarget::Collection { collection } => {
+        self.token_collection_by_id.as_ref().unwrap().get(token_id).as_ref() == Some(collection)
+      }
+      OfferTarget::Type { token_type } => {
+        self.token_type_by_id.as_ref().unwrap().get(token_id).as_ref() == Some(token_type)
+      }
+    };
+
+    assert!(is_target, "Token does not match the offer");
+  }
+
+  /// Escrow `amount` in `ft_token` (`None` for NEAR) from `buyer_id`
+  pub(crate) fn internal_offer_make(&mut self, buyer_id: &AccountId, target: OfferTarget, ft_token: Option<AccountId>, amount: Balance, expires_at: u64) -> Offer {
+    assert!(amount > 0, "Offer amount must be greater than 0");
+    assert!(expires_at > env::block_timestamp(), "Offer must expire in the future");
+
+    if let OfferTarget::Token { token_id } = &target {
+      let owner_id = self.owner_by_id.get(token_id).expect("Not found token");
+      assert_ne!(&owner_id, buyer_id, "Owner cannot make an offer");
+    }
+
+    let initial_storage_usage = env::storage_usage();
+    let mut offer = Offer {
+      id: self.offers_next_id,
+      buyer_id: buyer_id.clone(),
+      target,
+      ft_token,
+      amount: U128::from(amount),
+      expires_at,
+      storage: 0,
+    };
+    self.offers_next_id += 1;
+
+    let mut target_offers = self.offers_per_target.get(&offer.target).unwrap_or_else(|| {
+      UnorderedSet::new(StorageKey::OffersPerTargetInner {
+        target_hash: env::sha256(&offer.target.try_to_vec().unwrap()),
+      })
+    });
+    target_offers.insert(&offer.id);
+    self.offers_per_target.insert(&offer.target, &target_offers);
+
+    let mut buyer_offers = self.offers_per_buyer.get(buyer_id).unwrap_or_else(|| {
+      UnorderedSet::new(StorageKey::OffersPerBuyerInner {
+        account_hash: env::sha256(format!("offers:{}", buyer_id).as_bytes()),
+      })
+    });
+    buyer_offers.insert(&offer.id);
+    self.offers_per_buyer.insert(buyer_id, &buyer_offers);
+
+    self.offer_by_id.insert(&offer.id, &offer);
+    // the size of the offer does not depend on `storage`
+    offer.storage = storage_used_since(initial_storage_usage);
+    self.offer_by_id.insert(&offer.id, &offer);
+
+    OfferMake {
+      offer: &offer,
+    }.emit();
+
+    offer
+  }
+
+  pub(crate) fn internal_offer_remove(&mut self, offer_id: &OfferId) -> Offer {
+    let offer = self.offer_by_id.remove(offer_id).expect("Not found offer");
+
+    if let Some(mut target_offers) = self.offers_per_target.get(&offer.target) {
+      target_offers.remove(offer_id);
+
+      if target_offers.is_empty() {
+        self.offers_per_target.remove(&offer.target);
+      } else {
+        self.offers_per_target.insert(&offer.target, &target_offers);
+      }
+    }
+
+    if let Some(mut buyer_offers) = self.offers_per_buyer.get(&offer.buyer_id) {
+      buyer_offers.remove(offer_id);
+
+      if buyer_offers.is_empty() {
+        self.offers_per_buyer.remove(&offer.buyer_id);
+      } else {
+        self.offers_per_buyer.insert(&offer.buyer_id, &buyer_offers);
+      }
+    }
+
+    self.internal_storage_settle(&offer.buyer_id, offer.storage, 0);
+
+    offer
+  }
+
+  pub(crate) fn enum_get_offers(&self, offer_ids: Option<UnorderedSet<OfferId>>, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+    let offer_ids = if let Some(offer_ids) = offer_ids {
+      offer_ids
+    } else {
+      return vec![];
+    };
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    near_sdk::require!(limit != 0, "Cannot provide limit of 0.");
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    offer_ids
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .map(|offer_id| self.offer_by_id.get(&offer_id).unwrap())
+      .collect()
+  }
+}
@@ -0,0 +1,48 @@
+// Offer
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_offer {
+    ($contract: ident, $tokens: ident $(, $assert_transfer: ident, $assert_offer: ident)?) => {
+        use $crate::nft::offer::{NonFungibleTokenOffer, NonFungibleTokenOfferEnumeration, Offer, OfferId, OfferTarget};
+
+        #[near_bindgen]
+        impl NonFungibleTokenOffer for $contract {
+          #[payable]
+          fn nft_offer_make(&mut self, target: OfferTarget, amount: U128, expires_at: u64) -> Offer {
+            $(self.$assert_offer(&target);)?
+
+            self.$tokens.nft_offer_make(target, amount, expires_at)
+          }
+
+          fn nft_offer_cancel(&mut self, offer_id: OfferId) {
+            self.$tokens.nft_offer_cancel(offer_id)
+          }
+
+          #[payable]
+          fn nft_offer_accept(&mut self, offer_id: OfferId, token_id: TokenId) -> Offer {
+            $(self.$assert_transfer(&token_id, &env::predecessor_account_id());)?
+
+            self.$tokens.nft_offer_accept(offer_id, token_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenOfferEnumeration for $contract {
+          fn nft_offer(&self, offer_id: OfferId) -> Option<Offer> {
+            self.$tokens.nft_offer(offer_id)
+          }
+
+          fn nft_offers(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+            self.$tokens.nft_offers(from_index, limit)
+          }
+
+          fn nft_offers_by_target(&self, target: OfferTarget, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+            self.$tokens.nft_offers_by_target(target, from_index, limit)
+          }
+
+          fn nft_offers_by_buyer(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+            self.$tokens.nft_offers_by_buyer(account_id, from_index, limit)
+          }
+        }
+    };
+}
@@ -0,0 +1,30 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use crate::nft::{TokenId, TokenCollection, TokenType};
+
+pub type OfferId = u64;
+
+/// Single token or a floor offer for any token of a collection or a type
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OfferTarget {
+  Token { token_id: TokenId },
+  Collection { collection: TokenCollection },
+  Type { token_type: TokenType },
+}
+
+/// `ft_token` is `None` for offers in NEAR
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Offer {
+  pub id: OfferId,
+  pub buyer_id: AccountId,
+  pub target: OfferTarget,
+  pub ft_token: Option<AccountId>,
+  pub amount: U128,
+  pub expires_at: u64,
+  // bytes paid by the buyer, released when the offer is removed
+  pub storage: u64,
+}
@@ -0,0 +1,31 @@
+pub use metadata::*;
+use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::TokenId;
+
+pub mod metadata;
+pub mod offer_impl;
+mod internal;
+mod macros;
+
+/// Offers escrow NEAR or FT against a token, or against any token of a collection or type
+pub trait NonFungibleTokenOffer {
+  /// Offer `amount` of the attached deposit, valid until `expires_at`
+  fn nft_offer_make(&mut self, target: OfferTarget, amount: U128, expires_at: u64) -> Offer;
+
+  /// Buyer can cancel the offer any time, anyone once it is expired, the escrow goes back to the buyer
+  fn nft_offer_cancel(&mut self, offer_id: OfferId);
+
+  /// Sell the token of the caller to the offer buyer, requires 1 yoctoNEAR
+  fn nft_offer_accept(&mut self, offer_id: OfferId, token_id: TokenId) -> Offer;
+}
+
+pub trait NonFungibleTokenOfferEnumeration {
+  fn nft_offer(&self, offer_id: OfferId) -> Option<Offer>;
+
+  fn nft_offers(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer>;
+
+  fn nft_offers_by_target(&self, target: OfferTarget, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer>;
+
+  fn nft_offers_by_buyer(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer>;
+}
@@ -0,0 +1,180 @@
+use near_sdk::{env, require, assert_one_yocto, AccountId};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, refund_approved_account_ids, storage_used_since};
+use crate::nft::offer::{NonFungibleTokenOffer, NonFungibleTokenOfferEnumeration, Offer, OfferId, OfferTarget};
+use crate::nft::events_171_mf::{OfferCancel, OfferAccept};
+
+impl NonFungibleTokenOffer for NonFungibleToken {
+  fn nft_offer_make(&mut self, target: OfferTarget, amount: U128, expires_at: u64) -> Offer {
+    let buyer_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+
+    let offer = self.internal_offer_make(&buyer_id, target, None, amount.0, expires_at);
+
+    self.internal_storage_charge(&buyer_id, storage_used_since(initial_storage_usage), amount.0);
+
+    offer
+  }
+
+  fn nft_offer_cancel(&mut self, offer_id: OfferId) {
+    let offer = self.offer_by_id.get(&offer_id).expect("Not found offer");
+    let is_expired = env::block_timestamp() >= offer.expires_at;
+
+    assert!(is_expired || env::predecessor_account_id() == offer.buyer_id, "Unauthorized");
+
+    self.internal_offer_remove(&offer_id);
+
+    self.internal_payout_transfer(&offer.ft_token, &offer.buyer_id, offer.amount.0, format!("Offer {} refund", offer_id));
+
+    OfferCancel {
+      offer_id: &offer_id,
+      buyer_id: &offer.buyer_id,
+    }.emit();
+  }
+
+  fn nft_offer_accept(&mut self, offer_id: OfferId, token_id: TokenId) -> Offer {
+    assert_one_yocto();
+    let owner_id = self.assert_token_holder(&token_id);
+    let offer = self.internal_offer_remove(&offer_id);
+
+    assert!(env::block_timestamp() < offer.expires_at, "Offer is expired");
+    self.assert_offer_target(&offer.target, &token_id);
+    self.assert_transfer_fee_price(&token_id, &owner_id, &offer.buyer_id, offer.amount.0);
+
+    let (_, approved_account_ids) = self.internal_transfer(&owner_id, &offer.buyer_id, &token_id, None, None);
+
+    if let Some(approved_account_ids) = approved_account_ids {
+      refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+    }
+
+    let payout = self.internal_payout(&token_id, &owner_id, offer.amount.0, None);
+
+    payout
+      .iter()
+      .filter(|(_, amount)| amount.0 > 0)
+      .for_each(|(account_id, amount)| {
+        self.internal_payout_transfer(&offer.ft_token, account_id, amount.0, format!("Offer {} accepted", offer_id));
+      });
+
+    OfferAccept {
+      offer_id: &offer_id,
+      token_id: &token_id,
+      owner_id: &owner_id,
+      buyer_id: &offer.buyer_id,
+      amount: &offer.amount,
+    }.emit();
+
+    offer
+  }
+}
+
+impl NonFungibleTokenOfferEnumeration for NonFungibleToken {
+  fn nft_offer(&self, offer_id: OfferId) -> Option<Offer> {
+    self.offer_by_id.get(&offer_id)
+  }
+
+  fn nft_offers(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.offer_by_id.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.offer_by_id
+      .values()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+
+  fn nft_offers_by_target(&self, target: OfferTarget, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+    self.enum_get_offers(self.offers_per_target.get(&target), from_index, limit)
+  }
+
+  fn nft_offers_by_buyer(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Offer> {
+    self.enum_get_offers(self.offers_per_buyer.get(&account_id), from_index, limit)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::nft::{TokenCollection, TokenType, storage_cost};
+  use crate::nft::test_utils::*;
+
+  fn offer(tokens: &mut NonFungibleToken, buyer_id: &AccountId, target: OfferTarget, amount: u128) -> Offer {
+    set_context(context(buyer_id, amount + NEAR).block_timestamp(10));
+
+    tokens.nft_offer_make(target, U128::from(amount), 1_000)
+  }
+
+  #[test]
+  fn accept_collection_offer_transfers_token_and_pays_owner() {
+    let mut tokens = new_tokens();
+    let (seller, buyer) = (account("seller"), account("buyer"));
+    mint(&mut tokens, "token", Some(&seller), None);
+    let offer = offer(&mut tokens, &buyer, OfferTarget::Collection { collection: TokenCollection::Nordic }, 5 * NEAR);
+
+    set_context(context(&seller, 1).block_timestamp(20));
+    tokens.nft_offer_accept(offer.id, "token".to_string());
+
+    assert_eq!(tokens.owner_by_id.get(&"token".to_string()), Some(buyer.clone()));
+    // the buyer gets the storage of the offer back
+    assert_eq!(transfers(), vec![(buyer.clone(), storage_cost(offer.storage)), (seller, 5 * NEAR)]);
+    assert!(tokens.nft_offer(offer.id).is_none());
+    assert!(tokens.nft_offers_by_buyer(buyer, None, None).is_empty());
+  }
+
+  #[test]
+  #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+  fn accept_requires_one_yocto() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    mint(&mut tokens, "token", Some(&seller), None);
+    let offer = offer(&mut tokens, &account("buyer"), OfferTarget::Token { token_id: "token".to_string() }, 5 * NEAR);
+
+    set_context(context(&seller, 0).block_timestamp(20));
+    tokens.nft_offer_accept(offer.id, "token".to_string());
+  }
+
+  #[test]
+  #[should_panic(expected = "Token does not match the offer")]
+  fn accept_with_other_type_fails() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    mint(&mut tokens, "token", Some(&seller), None);
+    let offer = offer(&mut tokens, &account("buyer"), OfferTarget::Type { token_type: TokenType::Armor }, 5 * NEAR);
+
+    set_context(context(&seller, 1).block_timestamp(20));
+    tokens.nft_offer_accept(offer.id, "token".to_string());
+  }
+
+  #[test]
+  #[should_panic(expected = "Offer is expired")]
+  fn accept_expired_offer_fails() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    mint(&mut tokens, "token", Some(&seller), None);
+    let offer = offer(&mut tokens, &account("buyer"), OfferTarget::Token { token_id: "token".to_string() }, 5 * NEAR);
+
+    set_context(context(&seller, 1).block_timestamp(1_000));
+    tokens.nft_offer_accept(offer.id, "token".to_string());
+  }
+
+  #[test]
+  fn expired_offer_is_refunded_by_anyone() {
+    let mut tokens = new_tokens();
+    let buyer = account("buyer");
+    mint(&mut tokens, "token", Some(&account("seller")), None);
+    let offer = offer(&mut tokens, &buyer, OfferTarget::Token { token_id: "token".to_string() }, 5 * NEAR);
+
+    set_context(context(&account("anyone"), 0).block_timestamp(1_000));
+    tokens.nft_offer_cancel(offer.id);
+
+    assert_eq!(transfers(), vec![(buyer.clone(), storage_cost(offer.storage)), (buyer, 5 * NEAR)]);
+    assert!(tokens.nft_offer(offer.id).is_none());
+  }
+}
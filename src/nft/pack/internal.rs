@@ -0,0 +1,51 @@
+use near_sdk::{env, ext_contract, Balance, Gas, StorageUsage};
+use crate::nft::{NonFungibleToken, TokenId};
+use crate::nft::pack::Pack;
+
+const GAS_FOR_PACK_REVEAL: Gas = Gas(10_000_000_000_000);
+const GAS_FOR_PACK_REVEAL_ITEM: Gas = Gas(5_000_000_000_000);
+const NO_DEPOSIT: Balance = 0;
+/// Blocks after the opening when the pack can be revealed, later it has to be opened again
+pub(crate) const PACK_REVEAL_TIMEOUT: u64 = 1_000;
+
+#[ext_contract(ext_self)]
+trait PackRevealer {
+  fn nft_pack_reveal(&mut self, token_id: TokenId) -> Vec<TokenId>;
+}
+
+impl NonFungibleToken {
+  pub(crate) fn assert_pack_not_opening(&self, token_id: &TokenId) {
+    if let Some(pack) = self.pack_by_token.get(token_id) {
+      if let Some(open_block) = pack.open_block {
+        if env::block_height() <= open_block + PACK_REVEAL_TIMEOUT {
+          env::panic_str("Pack is being opened");
+        }
+      }
+    }
+  }
+
+  /// Remove the pack record and give its reserved items back to the sale
+  pub(crate) fn internal_pack_remove(&mut self, token_id: &TokenId) -> Option<Pack> {
+    let pack = self.pack_by_token.remove(token_id)?;
+
+    self.internal_sale_release_slots(&pack.sale_id, pack.items as u64);
+
+    Some(pack)
+  }
+
+  /// Settle storage of the items paid on opening, `used` bytes were taken by the reveal
+  pub(crate) fn internal_pack_storage_settle(&mut self, pack: &Pack, used: StorageUsage) {
+    if let Some((account_id, paid)) = &pack.storage {
+      self.internal_storage_settle(account_id, *paid, used);
+    }
+  }
+
+  pub(crate) fn internal_pack_schedule_reveal(&self, pack: &Pack) {
+    ext_self::nft_pack_reveal(
+      pack.token_id.clone(),
+      env::current_account_id(),
+      NO_DEPOSIT,
+      GAS_FOR_PACK_REVEAL + GAS_FOR_PACK_REVEAL_ITEM * pack.items as u64,
+    );
+  }
+}
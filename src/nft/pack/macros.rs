@@ -0,0 +1,34 @@
+// Pack
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_pack {
+    ($contract: ident, $tokens: ident $(, $assert_transfer: ident)?) => {
+        use $crate::nft::pack::{NonFungibleTokenPack, NonFungibleTokenPackEnumeration, Pack};
+
+        #[near_bindgen]
+        impl NonFungibleTokenPack for $contract {
+          fn nft_pack_create(&mut self, token_id: TokenId, sale_id: SaleId, items: u32) -> Pack {
+            self.$tokens.nft_pack_create(token_id, sale_id, items)
+          }
+
+          #[payable]
+          fn nft_pack_open(&mut self, token_id: TokenId) -> Pack {
+            $(self.$assert_transfer(&token_id, &env::predecessor_account_id());)?
+
+            self.$tokens.nft_pack_open(token_id)
+          }
+
+          #[private]
+          fn nft_pack_reveal(&mut self, token_id: TokenId) -> Vec<TokenId> {
+            self.$tokens.nft_pack_reveal(token_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenPackEnumeration for $contract {
+          fn nft_pack(&self, token_id: TokenId) -> Option<Pack> {
+            self.$tokens.nft_pack(token_id)
+          }
+        }
+    };
+}
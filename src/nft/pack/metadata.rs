@@ -0,0 +1,16 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::AccountId;
+use crate::nft::{TokenId, SaleId};
+
+/// Pack opens into `items` tokens of the sale `sale_id`, `open_block` is set while the pack is being opened.
+/// `storage` is the account that opened the pack and the bytes it paid for the items.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Pack {
+  pub token_id: TokenId,
+  pub sale_id: SaleId,
+  pub items: u32,
+  pub open_block: Option<u64>,
+  pub storage: Option<(AccountId, u64)>,
+}
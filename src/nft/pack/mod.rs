@@ -0,0 +1,23 @@
+pub use metadata::*;
+use crate::nft::{TokenId, SaleId};
+
+pub mod metadata;
+pub mod pack_impl;
+mod internal;
+mod macros;
+
+/// Packs are `Present` tokens burned for items of a not started sale. Items are reserved when the pack
+/// is created and drawn in a later block than the opening, so the contents cannot be simulated in advance.
+pub trait NonFungibleTokenPack {
+  fn nft_pack_create(&mut self, token_id: TokenId, sale_id: SaleId, items: u32) -> Pack;
+
+  /// Owner of the pack starts opening and pays storage of the items, the reveal is scheduled by the call itself
+  fn nft_pack_open(&mut self, token_id: TokenId) -> Pack;
+
+  /// Burn the opened pack and transfer the items to its owner, only the contract account can retry it until the opening expires
+  fn nft_pack_reveal(&mut self, token_id: TokenId) -> Vec<TokenId>;
+}
+
+pub trait NonFungibleTokenPackEnumeration {
+  fn nft_pack(&self, token_id: TokenId) -> Option<Pack>;
+}
@@ -0,0 +1,99 @@
+use near_sdk::env;
+use crate::nft::{NonFungibleToken, TokenId, SaleId, TokenType, storage_used_since};
+use crate::nft::pack::{NonFungibleTokenPack, NonFungibleTokenPackEnumeration, Pack};
+use crate::nft::pack::internal::PACK_REVEAL_TIMEOUT;
+use crate::nft::events_171_mf::{PackCreate, PackOpen, PackReveal};
+
+impl NonFungibleTokenPack for NonFungibleToken {
+  fn nft_pack_create(&mut self, token_id: TokenId, sale_id: SaleId, items: u32) -> Pack {
+    self.assert_owner();
+
+    self.owner_by_id.get(&token_id).expect("Not found token");
+    let token_type = self.token_type_by_id.as_ref().unwrap().get(&token_id).expect("Not found token");
+
+    assert_eq!(token_type, TokenType::Present, "Only Present tokens can be packs");
+    assert!(!self.pack_by_token.contains_key(&token_id), "Pack already exists");
+    assert!(items > 0, "Pack items must be greater than 0");
+    assert!(self.sales_locked.as_ref().unwrap().contains(&sale_id), "Pack items can be drawn only from a not started sale");
+    assert!(self.internal_sale_available(&sale_id) >= items as u64, "Insufficient amount of nft");
+
+    self.internal_sale_reserve_slots(&sale_id, items as u64);
+
+    let pack = Pack {
+      token_id,
+      sale_id,
+      items,
+      open_block: None,
+      storage: None,
+    };
+    self.pack_by_token.insert(&pack.token_id, &pack);
+
+    PackCreate {
+      pack: &pack,
+    }.emit();
+
+    pack
+  }
+
+  fn nft_pack_open(&mut self, token_id: TokenId) -> Pack {
+    let owner_id = self.owner_by_id.get(&token_id).expect("Not found token");
+    assert_eq!(env::predecessor_account_id(), owner_id, "Only owner can open the pack");
+
+    self.assert_pack_not_opening(&token_id);
+    self.assert_token_not_in_auction(&token_id);
+
+    let mut pack = self.pack_by_token.get(&token_id).expect("Not found pack");
+
+    // storage of the items is paid on opening, an expired opening is refunded first
+    self.internal_pack_storage_settle(&pack, 0);
+    let storage = self.internal_sale_token_storage(&pack.sale_id) * pack.items as u64;
+
+    pack.open_block = Some(env::block_height());
+    pack.storage = Some((owner_id.clone(), storage));
+    self.pack_by_token.insert(&token_id, &pack);
+
+    self.internal_storage_charge(&owner_id, storage, 0);
+
+    PackOpen {
+      token_id: &token_id,
+      owner_id: &owner_id,
+    }.emit();
+
+    self.internal_pack_schedule_reveal(&pack);
+
+    pack
+  }
+
+  fn nft_pack_reveal(&mut self, token_id: TokenId) -> Vec<TokenId> {
+    let pack = self.pack_by_token.get(&token_id).expect("Not found pack");
+    let open_block = pack.open_block.expect("Pack is not opened");
+    let block_height = env::block_height();
+
+    assert!(block_height > open_block, "Pack can be revealed only in a later block");
+    assert!(block_height <= open_block + PACK_REVEAL_TIMEOUT, "Pack opening is expired");
+
+    let owner_id = self.owner_by_id.get(&token_id).expect("Not found token");
+
+    self.internal_pack_remove(&token_id);
+    self.internal_burn_token_unguarded(&owner_id, &token_id);
+
+    let initial_storage_usage = env::storage_usage();
+    let token_ids = self.internal_random_tokens(&pack.sale_id, &pack.items, &owner_id);
+    self.internal_pack_storage_settle(&pack, storage_used_since(initial_storage_usage));
+
+    PackReveal {
+      token_id: &token_id,
+      owner_id: &owner_id,
+      sale_id: &pack.sale_id,
+      token_ids: &token_ids,
+    }.emit();
+
+    token_ids
+  }
+}
+
+impl NonFungibleTokenPackEnumeration for NonFungibleToken {
+  fn nft_pack(&self, token_id: TokenId) -> Option<Pack> {
+    self.pack_by_token.get(&token_id)
+  }
+}
@@ -0,0 +1,43 @@
+use near_sdk::{env, AccountId, PromiseResult};
+use near_sdk::json_types::U128;
+use crate::nft::NonFungibleToken;
+use crate::nft::payout::{PayoutClaims, PayoutClaimsResolver};
+
+impl PayoutClaims for NonFungibleToken {
+  fn nft_payout_claim(&mut self, ft_token: Option<AccountId>) -> U128 {
+    let account_id = env::predecessor_account_id();
+    let amount = self.payout_claims.remove(&(account_id.clone(), ft_token.clone())).expect("Nothing to claim");
+
+    self.internal_payout_transfer(&ft_token, &account_id, amount, "Payout claim".to_string());
+
+    U128::from(amount)
+  }
+
+  fn nft_payout_claimable(&self, account_id: AccountId, ft_token: Option<AccountId>) -> U128 {
+    U128::from(self.payout_claims.get(&(account_id, ft_token)).unwrap_or(0))
+  }
+}
+
+impl PayoutClaimsResolver for NonFungibleToken {
+  fn nft_resolve_payout(
+    &mut self,
+    ft_token: Option<AccountId>,
+    receiver_id: AccountId,
+    amount: U128,
+  ) -> U128 {
+    let success = match env::promise_result(0) {
+      PromiseResult::NotReady => env::abort(),
+      PromiseResult::Successful(_) => true,
+      PromiseResult::Failed => false,
+    };
+
+    if success {
+      return amount;
+    }
+
+    // transfer failed, the amount is back on the contract and can be claimed
+    self.internal_payout_claim_add(&ft_token, &receiver_id, amount.0);
+
+    U128::from(0)
+  }
+}
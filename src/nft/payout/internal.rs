@@ -0,0 +1,39 @@
+use near_sdk::{AccountId, env, ext_contract, Balance, Gas};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, transfer_near_or_ft};
+
+const GAS_FOR_RESOLVE_PAYOUT: Gas = Gas(5_000_000_000_000);
+const NO_DEPOSIT: Balance = 0;
+
+#[ext_contract(ext_self)]
+trait PayoutResolver {
+  fn nft_resolve_payout(
+    &mut self,
+    ft_token: Option<AccountId>,
+    receiver_id: AccountId,
+    amount: U128,
+  ) -> U128;
+}
+
+impl NonFungibleToken {
+  /// Send `amount` to `receiver_id`, it can be claimed later if the transfer fails
+  pub(crate) fn internal_payout_transfer(&self, ft_token: &Option<AccountId>, receiver_id: &AccountId, amount: Balance, memo: String) {
+    let transfer = transfer_near_or_ft(ft_token, receiver_id, amount, memo);
+
+    transfer.then(ext_self::nft_resolve_payout(
+      ft_token.clone(),
+      receiver_id.clone(),
+      U128::from(amount),
+      env::current_account_id(),
+      NO_DEPOSIT,
+      GAS_FOR_RESOLVE_PAYOUT,
+    ));
+  }
+
+  pub(crate) fn internal_payout_claim_add(&mut self, ft_token: &Option<AccountId>, account_id: &AccountId, amount: Balance) {
+    let key = (account_id.clone(), ft_token.clone());
+    let claimable = self.payout_claims.get(&key).unwrap_or(0);
+
+    self.payout_claims.insert(&key, &(claimable + amount));
+  }
+}
@@ -4,19 +4,39 @@
 macro_rules! impl_non_fungible_token_payout {
     ($contract: ident, $token: ident $(, $assert_transfer: ident)?) => {
         use $crate::{NonFungibleTokenPayout, Payout};
+        use $crate::nft::payout::{PayoutClaims, PayoutClaimsResolver};
 
         #[near_bindgen]
         impl NonFungibleTokenPayout for $contract {
           #[payable]
-          fn nft_transfer_payout(&mut self, receiver_id: AccountId, token_id: TokenId, approval_id: u64, balance: U128, max_len_payout: u32, memo: Option<String>) -> Payout {
+          fn nft_transfer_payout(&mut self, receiver_id: AccountId, token_id: TokenId, approval_id: Option<u64>, memo: Option<String>, balance: U128, max_len_payout: Option<u32>) -> Payout {
               $(self.$assert_transfer(&token_id, &receiver_id);)?
 
-              self.$token.nft_transfer_payout(receiver_id, token_id, approval_id, balance, max_len_payout, memo)
+              self.$token.nft_transfer_payout(receiver_id, token_id, approval_id, memo, balance, max_len_payout)
           }
 
-          fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: u32) -> Payout {
+          fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout {
               self.$token.nft_payout(token_id, balance, max_len_payout)
           }
         }
+
+        #[near_bindgen]
+        impl PayoutClaims for $contract {
+          fn nft_payout_claim(&mut self, ft_token: Option<AccountId>) -> U128 {
+              self.$token.nft_payout_claim(ft_token)
+          }
+
+          fn nft_payout_claimable(&self, account_id: AccountId, ft_token: Option<AccountId>) -> U128 {
+              self.$token.nft_payout_claimable(account_id, ft_token)
+          }
+        }
+
+        #[near_bindgen]
+        impl PayoutClaimsResolver for $contract {
+          #[private]
+          fn nft_resolve_payout(&mut self, ft_token: Option<AccountId>, receiver_id: AccountId, amount: U128) -> U128 {
+              self.$token.nft_resolve_payout(ft_token, receiver_id, amount)
+          }
+        }
     };
 }
@@ -4,18 +4,44 @@ use near_sdk::json_types::U128;
 use near_sdk::AccountId;
 
 pub mod payout_impl;
+pub mod claims_impl;
+mod internal;
 mod macros;
 
+/// NEP-199 payouts, royalty caps are checked when royalties are set so a payout only panics when
+/// it does not fit into `max_len_payout` receivers
 pub trait NonFungibleTokenPayout {
-  fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: u32) -> Payout;
+  /// Split of `balance` for a sale of the token by its current owner, the parts sum to `balance`
+  fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout;
 
+  /// Transfer the token like `nft_transfer` with exactly 1 yoctoNEAR attached and return the payout
+  /// of `balance` for the previous owner
   fn nft_transfer_payout(
     &mut self,
     receiver_id: AccountId,
     token_id: TokenId,
-    approval_id: u64,
-    balance: U128,
-    max_len_payout: u32,
+    approval_id: Option<u64>,
     memo: Option<String>,
+    balance: U128,
+    max_len_payout: Option<u32>,
   ) -> Payout;
 }
+
+/// Payouts of auctions, market listings, offers and vaults that failed to transfer stay on the
+/// contract until the receiver claims them
+pub trait PayoutClaims {
+  /// Send failed payouts of the caller in `ft_token`, `None` for NEAR
+  fn nft_payout_claim(&mut self, ft_token: Option<AccountId>) -> U128;
+
+  fn nft_payout_claimable(&self, account_id: AccountId, ft_token: Option<AccountId>) -> U128;
+}
+
+pub trait PayoutClaimsResolver {
+  /// Returns the paid amount, makes it claimable if the transfer failed
+  fn nft_resolve_payout(
+    &mut self,
+    ft_token: Option<AccountId>,
+    receiver_id: AccountId,
+    amount: U128,
+  ) -> U128;
+}
@@ -1,8 +1,6 @@
-use crate::nft::{NonFungibleToken, TokenId, refund_approved_account_ids};
+use crate::nft::{NonFungibleToken, TokenId, refund_approved_account_ids, storage_used_since};
 use near_sdk::json_types::U128;
 use crate::nft::payout::{NonFungibleTokenPayout};
-use crate::nft::royalty::royalty_to_payout;
-use crate::royalty::{MINTER_ROYALTY_CAP, CONTRACT_ROYALTY_CAP};
 use near_sdk::{env, assert_one_yocto, AccountId};
 use std::collections::HashMap;
 use crate::nft::events_171_mf::NftTransferPayout;
@@ -15,61 +13,42 @@ pub struct Payout {
 }
 
 impl NonFungibleTokenPayout for NonFungibleToken {
-  fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: u32) -> Payout {
-    let royalty = self.royalty.token_royalty_by_id.get(&token_id);
+  fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout {
     let owner_id = self.owner_by_id.get(&token_id).expect("No token");
 
-    // compute payouts based on balance option
-    // adds in contract_royalty and computes previous owner royalty from remainder
-    let mut total_perpetual = 0;
-    let balance_u128 = u128::from(balance);
-    let mut payout: Payout = Payout {
-      payout: HashMap::new(),
-    };
-
-    if let Some(royalty) = royalty {
-      assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
-
-      for (k, v) in royalty.iter() {
-        let key = k.clone();
-        if key != owner_id {
-          payout.payout.insert(key, royalty_to_payout(*v, balance_u128));
-          total_perpetual += *v;
-        }
-      }
-    }
-
-    // payout to contract owner - may be previous token owner, they get remainder of balance
-    if self.royalty.amount > 0 && self.royalty.receiver_id != owner_id {
-      payout.payout.insert(self.royalty.receiver_id.clone(), royalty_to_payout(self.royalty.amount, balance_u128));
-      total_perpetual += self.royalty.amount;
+    Payout {
+      payout: self.internal_payout(&token_id, &owner_id, u128::from(balance), max_len_payout),
     }
-
-    assert!(total_perpetual <= MINTER_ROYALTY_CAP + CONTRACT_ROYALTY_CAP, "Royalties should not be more than caps");
-    // payout to previous owner
-    payout.payout.insert(owner_id, royalty_to_payout(10000 - total_perpetual, balance_u128));
-
-    payout
   }
 
   fn nft_transfer_payout(
     &mut self,
     receiver_id: AccountId,
     token_id: TokenId,
-    approval_id: u64,
-    balance: U128,
-    max_len_payout: u32,
+    approval_id: Option<u64>,
     memo: Option<String>,
+    balance: U128,
+    max_len_payout: Option<u32>,
   ) -> Payout {
     assert_one_yocto();
     let sender_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+    // royalties are resolved before the transfer, the previous owner gets the rest
+    let owner_id = self.owner_by_id.get(&token_id).expect("No token");
+    self.assert_transfer_fee_price(&token_id, &owner_id, &receiver_id, balance.0);
+
+    let payout = Payout {
+      payout: self.internal_payout(&token_id, &owner_id, u128::from(balance), max_len_payout),
+    };
+
     let (owner_id, approved_account_ids) = self.internal_transfer(
       &sender_id,
       &receiver_id,
       &token_id,
-      Some(approval_id),
+      approval_id,
       memo,
     );
+    self.internal_storage_use_deposit(&sender_id, storage_used_since(initial_storage_usage), env::attached_deposit());
 
     if let Some(approved_account_ids) = approved_account_ids {
       refund_approved_account_ids(
@@ -78,34 +57,6 @@ impl NonFungibleTokenPayout for NonFungibleToken {
       );
     }
 
-    // compute payouts based on balance option
-    // adds in contract_royalty and computes previous owner royalty from remainder
-    let mut total_perpetual = 0;
-    let balance_u128 = u128::from(balance);
-    let mut payout: Payout = Payout { payout: HashMap::new() };
-    let royalty = self.royalty.token_royalty_by_id.get(&token_id);
-
-    if let Some(royalty) = royalty {
-      assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
-
-      for (k, v) in royalty.iter() {
-        let key = k.clone();
-        if key != owner_id {
-          payout.payout.insert(key, royalty_to_payout(*v, balance_u128));
-          total_perpetual += *v;
-        }
-      }
-    }
-
-    // payout to contract owner - may be previous token owner, they get remainder of balance
-    if self.royalty.amount > 0 && self.royalty.receiver_id != owner_id {
-      payout.payout.insert(self.royalty.receiver_id.clone(), royalty_to_payout(self.royalty.amount, balance_u128));
-      total_perpetual += self.royalty.amount;
-    }
-    assert!(total_perpetual <= MINTER_ROYALTY_CAP + CONTRACT_ROYALTY_CAP, "Royalties should not be more than caps");
-    // payout to previous owner
-    payout.payout.insert(owner_id.clone(), royalty_to_payout(10000 - total_perpetual, balance_u128));
-
     NftTransferPayout {
       token_id: &token_id,
       sender_id: &sender_id,
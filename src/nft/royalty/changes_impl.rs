@@ -0,0 +1,121 @@
+use near_sdk::{assert_one_yocto, env, require, AccountId};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, storage_used_since};
+use crate::nft::royalty::{Royalty, RoyaltyChange, RoyaltyChanges, RoyaltyProposal};
+use crate::nft::events_171_mf::RoyaltyPropose;
+
+impl RoyaltyChanges for NonFungibleToken {
+  fn nft_royalty_reassign(&mut self, token_id: TokenId, receiver_id: AccountId) -> Royalty {
+    assert_one_yocto();
+
+    let account_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+
+    self.owner_by_id.get(&token_id).expect("Not found token");
+
+    // shares of a default become own royalties of the token
+    let mut royalty = self.internal_token_royalty(&token_id);
+    let amount = royalty.remove(&account_id).expect("Only royalty recipient can reassign");
+
+    assert_ne!(account_id, receiver_id, "Royalty recipient must differ");
+    *royalty.entry(receiver_id).or_insert(0) += amount;
+
+    self.internal_royalty_update(&token_id, royalty.clone(), &account_id);
+
+    // consents of a pending proposal were given for the previous recipients
+    self.royalty_proposal_by_id.remove(&token_id);
+
+    self.internal_storage_use_registered(&account_id, storage_used_since(initial_storage_usage));
+
+    royalty
+  }
+
+  fn nft_royalty_propose(&mut self, token_id: TokenId, royalty: Royalty) -> RoyaltyProposal {
+    self.assert_owner();
+
+    self.owner_by_id.get(&token_id).expect("Not found token");
+    let royalty = self.royalty.internal_royalty_calculate(Some(royalty));
+    let previous = self.internal_token_royalty(&token_id);
+
+    let mut consents_required: Vec<AccountId> = previous
+      .iter()
+      .filter(|(account_id, amount)| royalty.get(account_id).unwrap_or(&0) < amount)
+      .map(|(account_id, _)| account_id.clone())
+      .collect();
+    consents_required.sort();
+
+    let proposal = RoyaltyProposal {
+      royalty,
+      consents_required,
+      consents: vec![],
+      created_at: env::block_timestamp(),
+    };
+
+    RoyaltyPropose {
+      token_id: &token_id,
+      proposal: &proposal,
+    }.emit();
+
+    if proposal.consents_required.is_empty() {
+      self.royalty_proposal_by_id.remove(&token_id);
+      self.internal_royalty_update(&token_id, proposal.royalty.clone(), &env::predecessor_account_id());
+    } else {
+      self.royalty_proposal_by_id.insert(&token_id, &proposal);
+    }
+
+    proposal
+  }
+
+  fn nft_royalty_proposal_cancel(&mut self, token_id: TokenId) {
+    self.assert_owner();
+
+    self.royalty_proposal_by_id.remove(&token_id).expect("Not found royalty proposal");
+  }
+
+  fn nft_royalty_consent(&mut self, token_id: TokenId) -> RoyaltyProposal {
+    assert_one_yocto();
+
+    let account_id = env::predecessor_account_id();
+    let mut proposal = self.royalty_proposal_by_id.get(&token_id).expect("Not found royalty proposal");
+
+    assert!(proposal.consents_required.contains(&account_id), "Consent is not required");
+    assert!(!proposal.consents.contains(&account_id), "Already consented");
+
+    proposal.consents.push(account_id);
+
+    if proposal.consents.len() == proposal.consents_required.len() {
+      self.royalty_proposal_by_id.remove(&token_id);
+      self.internal_royalty_update(&token_id, proposal.royalty.clone(), &self.owner_id.clone());
+    } else {
+      self.royalty_proposal_by_id.insert(&token_id, &proposal);
+    }
+
+    proposal
+  }
+
+  fn nft_royalty_proposal(&self, token_id: TokenId) -> Option<RoyaltyProposal> {
+    self.royalty_proposal_by_id.get(&token_id)
+  }
+
+  fn nft_royalty_history(&self, token_id: TokenId, from_index: Option<U128>, limit: Option<u64>) -> Vec<RoyaltyChange> {
+    let history = match self.royalty_history_by_id.get(&token_id) {
+      Some(history) => history,
+      None => return vec![],
+    };
+
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (history.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    history
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+}
@@ -0,0 +1,61 @@
+use crate::nft::{NonFungibleToken, TokenId, TokenCollection, TokenType};
+use crate::nft::royalty::{Royalty, RoyaltyDefaults};
+use crate::nft::events_171_mf::RoyaltyDefaultUpdate;
+
+impl RoyaltyDefaults for NonFungibleToken {
+  fn nft_set_collection_royalty(&mut self, collection: TokenCollection, royalty: Option<Royalty>) {
+    self.assert_owner();
+
+    match &royalty {
+      Some(royalty) => {
+        self.royalty.internal_royalty_calculate(Some(royalty.clone()));
+        self.royalty_by_collection.insert(&collection, royalty)
+      }
+      None => self.royalty_by_collection.remove(&collection),
+    };
+
+    RoyaltyDefaultUpdate {
+      collection: Some(&collection),
+      token_type: None,
+      royalty: royalty.as_ref(),
+    }.emit();
+  }
+
+  fn nft_set_type_royalty(&mut self, token_type: TokenType, royalty: Option<Royalty>) {
+    self.assert_owner();
+
+    match &royalty {
+      Some(royalty) => {
+        self.royalty.internal_royalty_calculate(Some(royalty.clone()));
+        self.royalty_by_type.insert(&token_type, royalty)
+      }
+      None => self.royalty_by_type.remove(&token_type),
+    };
+
+    RoyaltyDefaultUpdate {
+      collection: None,
+      token_type: Some(&token_type),
+      royalty: royalty.as_ref(),
+    }.emit();
+  }
+
+  fn nft_collection_royalty(&self, collection: TokenCollection) -> Option<Royalty> {
+    self.royalty_by_collection.get(&collection)
+  }
+
+  fn nft_type_royalty(&self, token_type: TokenType) -> Option<Royalty> {
+    self.royalty_by_type.get(&token_type)
+  }
+
+  fn nft_token_royalty(&self, token_id: TokenId) -> Royalty {
+    self.owner_by_id.get(&token_id).expect("Not found token");
+
+    let mut royalty = self.internal_token_royalty(&token_id);
+
+    if self.royalty.amount > 0 {
+      *royalty.entry(self.royalty.receiver_id.clone()).or_insert(0) += self.royalty.amount;
+    }
+
+    royalty
+  }
+}
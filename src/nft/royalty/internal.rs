@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use near_sdk::{AccountId, Balance, BorshStorageKey, env};
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::collections::Vector;
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId};
+use crate::nft::royalty::{Royalty, RoyaltyChange};
+use crate::nft::events_171_mf::RoyaltyUpdate;
+
+/// Hashed values are tagged, fractionations use the same variant index for keys hashed by token
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum StorageKey {
+  RoyaltyHistoryInner { token_hash: Vec<u8> },
+}
+
+impl NonFungibleToken {
+  /// Royalties of the token itself, otherwise the default of its collection, otherwise the default of its type
+  pub(crate) fn internal_token_royalty(&self, token_id: &TokenId) -> Royalty {
+    if let Some(royalty) = self.royalty.token_royalty_by_id.get(token_id) {
+      return royalty;
+    }
+
+    let collection_royalty = self.token_collection_by_id.as_ref().unwrap()
+      .get(token_id)
+      .and_then(|collection| self.royalty_by_collection.get(&collection));
+
+    if let Some(royalty) = collection_royalty {
+      return royalty;
+    }
+
+    self.token_type_by_id.as_ref().unwrap()
+      .get(token_id)
+      .and_then(|token_type| self.royalty_by_type.get(&token_type))
+      .unwrap_or_default()
+  }
+
+  /// Replace the token royalties and record the change in the history of the token
+  pub(crate) fn internal_royalty_update(&mut self, token_id: &TokenId, royalty: Royalty, changed_by: &AccountId) {
+    let previous = self.internal_token_royalty(token_id);
+
+    // kept even when empty, otherwise defaults of the collection or type apply again
+    self.royalty.token_royalty_by_id.insert(token_id, &royalty);
+
+    let change = RoyaltyChange {
+      previous,
+      royalty,
+      changed_by: changed_by.clone(),
+      changed_at: env::block_timestamp(),
+    };
+
+    let mut history = self.royalty_history_by_id.get(token_id).unwrap_or_else(|| {
+      Vector::new(StorageKey::RoyaltyHistoryInner {
+        token_hash: env::sha256(format!("royalty_history:{}", token_id).as_bytes()),
+      })
+    });
+    history.push(&change);
+    self.royalty_history_by_id.insert(token_id, &history);
+
+    RoyaltyUpdate {
+      token_id,
+      previous: &change.previous,
+      royalty: &change.royalty,
+      changed_by,
+    }.emit();
+  }
+
+  /// Split `balance` for a sale of the token by `owner_id`
+  pub(crate) fn internal_payout(&self, token_id: &TokenId, owner_id: &AccountId, balance: Balance, max_len_payout: Option<u32>) -> HashMap<AccountId, U128> {
+    let royalty = self.internal_token_royalty(token_id);
+
+    self.royalty.internal_payout(&royalty, owner_id, balance, max_len_payout)
+  }
+}
@@ -25,3 +25,62 @@ macro_rules! impl_royalty_feature {
         }
     };
 }
+
+#[macro_export]
+macro_rules! impl_royalty_defaults {
+    ($contract: ident, $tokens: ident) => {
+        use $crate::royalty::{RoyaltyDefaults};
+
+        #[near_bindgen]
+        impl RoyaltyDefaults for $contract {
+          fn nft_set_collection_royalty(&mut self, collection: TokenCollection, royalty: Option<Royalty>) {
+            self.$tokens.nft_set_collection_royalty(collection, royalty)
+          }
+          fn nft_set_type_royalty(&mut self, token_type: TokenType, royalty: Option<Royalty>) {
+            self.$tokens.nft_set_type_royalty(token_type, royalty)
+          }
+
+          fn nft_collection_royalty(&self, collection: TokenCollection) -> Option<Royalty> {
+            self.$tokens.nft_collection_royalty(collection)
+          }
+          fn nft_type_royalty(&self, token_type: TokenType) -> Option<Royalty> {
+            self.$tokens.nft_type_royalty(token_type)
+          }
+          fn nft_token_royalty(&self, token_id: TokenId) -> Royalty {
+            self.$tokens.nft_token_royalty(token_id)
+          }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_royalty_changes {
+    ($contract: ident, $tokens: ident) => {
+        use $crate::royalty::{RoyaltyChanges, RoyaltyProposal, RoyaltyChange};
+
+        #[near_bindgen]
+        impl RoyaltyChanges for $contract {
+          #[payable]
+          fn nft_royalty_reassign(&mut self, token_id: TokenId, receiver_id: AccountId) -> Royalty {
+            self.$tokens.nft_royalty_reassign(token_id, receiver_id)
+          }
+          fn nft_royalty_propose(&mut self, token_id: TokenId, royalty: Royalty) -> RoyaltyProposal {
+            self.$tokens.nft_royalty_propose(token_id, royalty)
+          }
+          fn nft_royalty_proposal_cancel(&mut self, token_id: TokenId) {
+            self.$tokens.nft_royalty_proposal_cancel(token_id)
+          }
+          #[payable]
+          fn nft_royalty_consent(&mut self, token_id: TokenId) -> RoyaltyProposal {
+            self.$tokens.nft_royalty_consent(token_id)
+          }
+
+          fn nft_royalty_proposal(&self, token_id: TokenId) -> Option<RoyaltyProposal> {
+            self.$tokens.nft_royalty_proposal(token_id)
+          }
+          fn nft_royalty_history(&self, token_id: TokenId, from_index: Option<U128>, limit: Option<u64>) -> Vec<RoyaltyChange> {
+            self.$tokens.nft_royalty_history(token_id, from_index, limit)
+          }
+        }
+    };
+}
@@ -0,0 +1,23 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::AccountId;
+use crate::nft::royalty::Royalty;
+
+/// Royalties proposed by the contract owner, applied once every account in `consents_required` has consented
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoyaltyProposal {
+  pub royalty: Royalty,
+  pub consents_required: Vec<AccountId>,
+  pub consents: Vec<AccountId>,
+  pub created_at: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoyaltyChange {
+  pub previous: Royalty,
+  pub royalty: Royalty,
+  pub changed_by: AccountId,
+  pub changed_at: u64,
+}
@@ -1,7 +1,14 @@
 pub use royalty_impl::*;
+pub use metadata::*;
 use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::{TokenId, TokenCollection, TokenType};
 
 pub mod royalty_impl;
+pub mod defaults_impl;
+pub mod changes_impl;
+pub mod metadata;
+mod internal;
 mod macros;
 
 pub trait ContractRoyalty {
@@ -11,3 +18,40 @@ pub trait ContractRoyalty {
   fn nft_royalty_value(&self) -> u32;
   fn nft_royalty_account(&self) -> AccountId;
 }
+
+/// Defaults apply to tokens minted without own royalties, the collection default takes precedence
+/// over the token type default. The contract royalty is paid on top of either.
+pub trait RoyaltyDefaults {
+  /// `None` removes the default
+  fn nft_set_collection_royalty(&mut self, collection: TokenCollection, royalty: Option<Royalty>);
+
+  fn nft_set_type_royalty(&mut self, token_type: TokenType, royalty: Option<Royalty>);
+
+  fn nft_collection_royalty(&self, collection: TokenCollection) -> Option<Royalty>;
+
+  fn nft_type_royalty(&self, token_type: TokenType) -> Option<Royalty>;
+
+  /// Royalties paid on a sale of the token, including the contract royalty
+  fn nft_token_royalty(&self, token_id: TokenId) -> Royalty;
+}
+
+/// Royalties of a minted token change only with consent of the recipients whose shares decrease.
+/// Every change is kept in the history of the token.
+pub trait RoyaltyChanges {
+  /// Recipient moves its own share of the token royalties to `receiver_id`, a share of a default
+  /// moves with the rest of the default to the token royalties.
+  /// Requires 1 yoctoNEAR, storage is paid from the storage balance of the recipient
+  fn nft_royalty_reassign(&mut self, token_id: TokenId, receiver_id: AccountId) -> Royalty;
+
+  /// Contract owner proposes new token royalties within the caps, replaces the previous proposal
+  fn nft_royalty_propose(&mut self, token_id: TokenId, royalty: Royalty) -> RoyaltyProposal;
+
+  fn nft_royalty_proposal_cancel(&mut self, token_id: TokenId);
+
+  /// Recipient consents to the proposal with 1 yoctoNEAR, the last required consent applies it
+  fn nft_royalty_consent(&mut self, token_id: TokenId) -> RoyaltyProposal;
+
+  fn nft_royalty_proposal(&self, token_id: TokenId) -> Option<RoyaltyProposal>;
+
+  fn nft_royalty_history(&self, token_id: TokenId, from_index: Option<U128>, limit: Option<u64>) -> Vec<RoyaltyChange>;
+}
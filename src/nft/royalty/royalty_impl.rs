@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use near_sdk::{AccountId, Balance, IntoStorageKey};
 use near_sdk::json_types::U128;
 
@@ -19,8 +19,42 @@ pub const CONTRACT_ROYALTY_CAP: u32 = 1000;
 
 pub type Royalty = HashMap<AccountId, u32>;
 
+/// Basis points `a` of `b` rounded down, does not overflow for any balance
 pub(crate) fn royalty_to_payout(a: u32, b: Balance) -> U128 {
-  U128(a as u128 * b / 10_000u128)
+  U128(b / 10_000 * a as u128 + b % 10_000 * a as u128 / 10_000)
+}
+
+/// Split `balance` by basis points of `royalties`, amounts of the same account are merged.
+/// The owner gets the rest including the rounding remainder, so the parts always sum to `balance`.
+/// Receivers of zero amounts are left out and do not count towards `max_len_payout`.
+pub(crate) fn payout_split<'a, I>(owner_id: &AccountId, royalties: I, balance: Balance, max_len_payout: Option<u32>) -> HashMap<AccountId, U128>
+  where I: IntoIterator<Item = (&'a AccountId, u32)>
+{
+  let mut shares: BTreeMap<&AccountId, u32> = BTreeMap::new();
+
+  royalties
+    .into_iter()
+    .filter(|(account_id, amount)| *account_id != owner_id && *amount > 0)
+    .for_each(|(account_id, amount)| *shares.entry(account_id).or_insert(0) += amount);
+
+  // caps are checked when royalties are set, together they are far below 100%
+  let total_perpetual: u32 = shares.values().sum();
+  assert!(total_perpetual <= 10_000, "Royalties cannot be more than 100%");
+
+  let mut payout: HashMap<AccountId, U128> = shares
+    .into_iter()
+    .map(|(account_id, amount)| (account_id.clone(), royalty_to_payout(amount, balance)))
+    .filter(|(_, amount)| amount.0 > 0)
+    .collect();
+
+  let paid: Balance = payout.values().map(|amount| amount.0).sum();
+  payout.insert(owner_id.clone(), U128(balance - paid));
+
+  if let Some(max_len_payout) = max_len_payout {
+    assert!(payout.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
+  }
+
+  payout
 }
 
 impl RoyaltyFeature {
@@ -54,6 +88,18 @@ impl RoyaltyFeature {
     royalty
 
   }
+
+  /// Split `balance` between `royalty`, the contract royalty and the token owner who gets the remainder
+  pub(crate) fn internal_payout(&self, royalty: &Royalty, owner_id: &AccountId, balance: Balance, max_len_payout: Option<u32>) -> HashMap<AccountId, U128> {
+    let contract_royalty = Some((&self.receiver_id, self.amount));
+
+    payout_split(
+      owner_id,
+      royalty.iter().map(|(account_id, amount)| (account_id, *amount)).chain(contract_royalty),
+      balance,
+      max_len_payout,
+    )
+  }
 }
 
 impl ContractRoyalty for RoyaltyFeature {
@@ -76,3 +122,97 @@ impl ContractRoyalty for RoyaltyFeature {
     self.receiver_id.clone()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::prelude::*;
+
+  fn account(index: u8) -> AccountId {
+    format!("account{}.near", index).parse().unwrap()
+  }
+
+  fn royalties() -> impl Strategy<Value = Vec<(u8, u32)>> {
+    prop::collection::vec((0u8..6, 0u32..=500), 0..7)
+  }
+
+  fn split(owner: u8, royalties: &[(u8, u32)], balance: Balance, max_len_payout: Option<u32>) -> HashMap<AccountId, U128> {
+    let accounts: Vec<(AccountId, u32)> = royalties.iter().map(|(index, amount)| (account(*index), *amount)).collect();
+
+    payout_split(&account(owner), accounts.iter().map(|(account_id, amount)| (account_id, *amount)), balance, max_len_payout)
+  }
+
+  proptest! {
+    #[test]
+    fn parts_sum_to_balance(owner in 0u8..6, royalties in royalties(), balance in any::<u128>()) {
+      let payout = split(owner, &royalties, balance, None);
+
+      prop_assert_eq!(payout.values().map(|amount| amount.0).sum::<u128>(), balance);
+    }
+
+    #[test]
+    fn receivers_get_merged_basis_points(owner in 0u8..6, royalties in royalties(), balance in any::<u128>()) {
+      let payout = split(owner, &royalties, balance, None);
+
+      for index in (0u8..6).filter(|index| *index != owner) {
+        let amount: u32 = royalties.iter().filter(|(i, _)| *i == index).map(|(_, amount)| amount).sum();
+        let expected = Some(royalty_to_payout(amount, balance)).filter(|amount| amount.0 > 0);
+
+        prop_assert_eq!(payout.get(&account(index)).cloned(), expected);
+      }
+    }
+
+    #[test]
+    fn owner_gets_at_least_its_share(owner in 0u8..6, royalties in royalties(), balance in any::<u128>()) {
+      let payout = split(owner, &royalties, balance, None);
+      let others: u32 = royalties.iter().filter(|(index, _)| *index != owner).map(|(_, amount)| amount).sum();
+
+      prop_assert!(payout[&account(owner)].0 >= royalty_to_payout(10_000 - others, balance).0);
+    }
+
+    #[test]
+    fn order_does_not_change_payout(owner in 0u8..6, royalties in royalties(), balance in any::<u128>()) {
+      let mut reversed = royalties.clone();
+      reversed.reverse();
+
+      prop_assert_eq!(split(owner, &royalties, balance, None), split(owner, &reversed, balance, None));
+    }
+
+    #[test]
+    fn max_len_payout_counts_every_receiver(owner in 0u8..6, royalties in royalties(), balance in any::<u128>()) {
+      let len = split(owner, &royalties, balance, None).len() as u32;
+
+      prop_assert_eq!(split(owner, &royalties, balance, Some(len)).len() as u32, len);
+      prop_assert!(std::panic::catch_unwind(|| split(owner, &royalties, balance, Some(len - 1))).is_err());
+    }
+
+    #[test]
+    fn royalty_to_payout_is_floor(amount in 0u32..=10_000, balance in 0u128..=(u128::MAX / 10_000)) {
+      prop_assert_eq!(royalty_to_payout(amount, balance).0, amount as u128 * balance / 10_000);
+    }
+  }
+
+  #[test]
+  fn royalties_over_caps_are_paid() {
+    let payout = split(0, &[(1, 2000), (2, 1001)], 10_000, None);
+
+    assert_eq!(payout[&account(1)].0, 2000);
+    assert_eq!(payout[&account(2)].0, 1001);
+    assert_eq!(payout[&account(0)].0, 6999);
+  }
+
+  #[test]
+  fn dust_receivers_are_left_out() {
+    let payout = split(0, &[(1, 100), (2, 1)], 100, Some(2));
+
+    assert_eq!(payout.len(), 2);
+    assert_eq!(payout[&account(1)].0, 1);
+    assert_eq!(payout[&account(0)].0, 99);
+  }
+
+  #[test]
+  #[should_panic(expected = "Market cannot payout to that many receivers")]
+  fn zero_max_len_payout() {
+    split(0, &[], 100, Some(0));
+  }
+}
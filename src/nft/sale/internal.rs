@@ -1,14 +1,55 @@
-use near_sdk::{AccountId, env, BorshStorageKey};
-use near_sdk::collections::{UnorderedSet, LookupMap};
+use near_sdk::{AccountId, env, ext_contract, BorshStorageKey, Balance, CryptoHash, Gas, StorageUsage};
+use near_sdk::collections::{UnorderedSet, LookupMap, Vector};
+use near_sdk::json_types::{U128, Base58CryptoHash};
 use crate::{SaleId};
-use crate::nft::{TokenId, NonFungibleToken, JsonSale, random_use};
+use crate::nft::{TokenId, NonFungibleToken, JsonSale, random_use, transfer_near_or_ft, verify_merkle_proof, storage_used_since};
+use crate::nft::sale::{SaleProceeds, SalePhase, DutchAuction, SaleReservation, SaleReservationId, SaleTemplate, DropTable, DropKey};
+use crate::nft::metadata::TokenMetadata;
+use std::convert::TryInto;
+use crate::nft::events_171_mf::{SaleRevenue, SaleReserve, SaleReveal, SaleRefund};
 use near_sdk::borsh::{self, BorshSerialize};
 use rand::Rng;
+use rand::StdRng;
+
+const GAS_FOR_RESOLVE_SALE_WITHDRAW: Gas = Gas(5_000_000_000_000);
+const GAS_FOR_SALE_REVEAL: Gas = Gas(10_000_000_000_000);
+const GAS_FOR_SALE_REVEAL_TOKEN: Gas = Gas(5_000_000_000_000);
+const NO_DEPOSIT: Balance = 0;
+/// Blocks after the reservation when it can be revealed, later it can only be refunded
+const SALE_REVEAL_TIMEOUT: u64 = 1_000;
+/// Records of a revealed token besides the ones measured in `extra_storage_in_bytes_per_token`:
+/// rarity, collection, type, sub type, sale and royalty keyed by a token id of up to 64 bytes
+const TOKEN_RECORDS_STORAGE: StorageUsage = 6 * (40 + 4 + 64 + 64);
+
+#[ext_contract(ext_self)]
+trait SaleWithdrawResolver {
+  fn nft_resolve_sale_withdraw(
+    &mut self,
+    sale_id: SaleId,
+    ft_token: Option<AccountId>,
+    receiver_id: AccountId,
+    amount: U128,
+  ) -> U128;
+
+  fn nft_resolve_sale_rebate(
+    &mut self,
+    sale_id: SaleId,
+    account_id: AccountId,
+    count: u64,
+    paid: U128,
+    amount: U128,
+  ) -> U128;
+
+  fn nft_sale_reveal(&mut self, reservation_id: SaleReservationId) -> Vec<TokenId>;
+}
 
 #[derive(BorshSerialize, BorshStorageKey)]
 pub enum StorageKey {
   SaleTokensInner { sale_hash: Vec<u8> },
   MintCounterPerSale { sale_hash: Vec<u8> },
+  SaleRandomTokensInner { sale_hash: Vec<u8> },
+  SaleDropPoolInner { sale_hash: Vec<u8>, entry: u32 },
+  SaleReservationsPerAccount { account_hash: Vec<u8> },
 }
 
 impl NonFungibleToken {
@@ -29,6 +70,25 @@ impl NonFungibleToken {
     }
   }
 
+  pub(crate) fn assert_sale_not_ended(&self, sale_id: &SaleId) {
+    let sale = self.sale_by_id.as_ref().unwrap().get(sale_id).expect("Not found sale");
+
+    if let Some(end_date) = sale.end_date {
+      if env::block_timestamp() >= end_date {
+        env::panic_str("Sale is ended");
+      }
+    }
+  }
+
+  pub(crate) fn assert_sale_ended(&self, sale_id: &SaleId) {
+    let sale = self.sale_by_id.as_ref().unwrap().get(sale_id).expect("Not found sale");
+    let end_date = sale.end_date.expect("Sale has no end date");
+
+    if env::block_timestamp() < end_date {
+      env::panic_str("Sale is not ended");
+    }
+  }
+
   pub(crate) fn assert_token_not_in_sale(&self, token_id: &TokenId) {
     let sale_id = self.sale_by_token.as_ref().unwrap().get(&token_id);
 
@@ -49,9 +109,8 @@ impl NonFungibleToken {
 
     let mut start_date: Option<u64> = None;
     let mut not_minted = sale.amount;
-    let rand_tokens = self.sale_random_tokens.as_ref().unwrap().get(sale_id);
-    if let Some(rand_tokens) = rand_tokens {
-      not_minted = rand_tokens.len() as u64;
+    if let Some(rest_amount) = self.internal_sale_not_minted(sale_id) {
+      not_minted = rest_amount - self.sale_reserved_by_id.get(sale_id).unwrap_or(0);
     }
     if let Some(date) = date {
       start_date = Some(date.clone());
@@ -68,18 +127,155 @@ impl NonFungibleToken {
       not_minted,
       locked,
       start_date,
+      end_date: sale.end_date,
       ft_token,
+      current_phase: self.internal_sale_current_phase(sale_id),
+    }
+  }
+
+  pub(crate) fn internal_sale_current_phase(&self, sale_id: &SaleId) -> Option<SalePhase> {
+    let now = env::block_timestamp();
+
+    self.sale_phases_by_id
+      .get(sale_id)
+      .unwrap_or_default()
+      .into_iter()
+      .find(|phase| phase.start_date <= now && now < phase.end_date)
+  }
+
+  /// Price of one token, the current phase price overrides the dutch auction and the sale price
+  pub(crate) fn internal_sale_price(&self, sale_id: &SaleId) -> Balance {
+    if let Some(phase) = self.internal_sale_current_phase(sale_id) {
+      return phase.price.0;
+    }
+
+    match self.sale_dutch_by_id.get(sale_id) {
+      Some(auction) => self.internal_dutch_auction_price(sale_id, &auction),
+      None => self.sale_by_id.as_ref().unwrap().get(sale_id).expect("Not found sale").price.0,
     }
   }
 
+  pub(crate) fn internal_dutch_auction_price(&self, sale_id: &SaleId, auction: &DutchAuction) -> Balance {
+    let now = env::block_timestamp();
+
+    match self.sale_date_by_id.as_ref().unwrap().get(sale_id) {
+      Some(start) if now > *start => {
+        let steps = ((now - start) / auction.interval) as u128;
+        let discount = steps.saturating_mul(auction.step.0);
+
+        std::cmp::max(auction.floor_price.0, auction.start_price.0.saturating_sub(discount))
+      }
+      _ => auction.start_price.0,
+    }
+  }
+
+  /// Record a purchase at the dutch auction price for rebates
+  pub(crate) fn internal_dutch_auction_purchase(&mut self, sale_id: &SaleId, account_id: &AccountId, amount: &u64, price: Balance) {
+    if *amount == 0 {
+      return;
+    }
+
+    match self.sale_dutch_by_id.get(sale_id) {
+      Some(auction) if auction.rebate => {}
+      _ => return,
+    }
+
+    let key = (sale_id.clone(), account_id.clone());
+    let (count, paid) = self.sale_dutch_purchases.get(&key).unwrap_or((0, 0));
+    self.sale_dutch_purchases.insert(&key, &(count + amount, paid + price * (*amount as u128)));
+
+    let mut totals = self.sale_dutch_totals.get(sale_id).unwrap_or_default();
+    // reveals may land out of order, the clearing price is the lowest one
+    totals.clearing_price = if totals.sold == 0 {
+      price
+    } else {
+      std::cmp::min(totals.clearing_price, price)
+    };
+    totals.sold += amount;
+    totals.paid += price * (*amount as u128);
+    self.sale_dutch_totals.insert(sale_id, &totals);
+  }
+
+  /// Clearing price is final when the sale is sold out or ended
+  pub(crate) fn internal_dutch_auction_cleared(&self, sale_id: &SaleId) -> bool {
+    let sale = self.sale_by_id.as_ref().unwrap().get(sale_id).expect("Not found sale");
+    let sold_out = self.internal_sale_not_minted(sale_id).map(|rest_amount| rest_amount == 0).unwrap_or(false)
+      && self.sale_reserved_by_id.get(sale_id).is_none();
+    let ended = sale.end_date.map(|end_date| env::block_timestamp() >= end_date).unwrap_or(false);
+
+    sold_out || ended
+  }
+
+  /// Part of sale proceeds that may still be claimed back as rebates
+  pub(crate) fn internal_dutch_auction_reserved(&self, sale_id: &SaleId) -> Balance {
+    let auction = match self.sale_dutch_by_id.get(sale_id) {
+      Some(auction) if auction.rebate => auction,
+      _ => return 0,
+    };
+    let totals = match self.sale_dutch_totals.get(sale_id) {
+      Some(totals) => totals,
+      None => return 0,
+    };
+
+    let lowest_price = if self.internal_dutch_auction_cleared(sale_id) {
+      totals.clearing_price
+    } else {
+      auction.floor_price.0
+    };
+
+    totals.paid.saturating_sub(lowest_price * (totals.sold as u128))
+  }
+
+  pub(crate) fn internal_sale_phase_use(&mut self, receiver_id: &AccountId, sale_id: &SaleId, phase: &SalePhase, amount: &u32, proof: &Option<Vec<Base58CryptoHash>>) {
+    if let Some(merkle_root) = phase.merkle_root {
+      let leaf: CryptoHash = env::sha256(receiver_id.as_bytes()).try_into().unwrap();
+      let proof: Vec<CryptoHash> = proof
+        .as_ref()
+        .expect("Proof is required")
+        .iter()
+        .map(|node| CryptoHash::from(*node))
+        .collect();
+
+      assert!(verify_merkle_proof(&merkle_root.into(), leaf, &proof), "Account is not allowed in phase {}", phase.name);
+    }
+
+    let key = (sale_id.clone(), phase.name.clone(), receiver_id.clone());
+    let minted = self.sale_phase_mint_counter.get(&key).unwrap_or(0);
+
+    assert!(minted + amount <= phase.buy_max, "Phase mint limit");
+
+    self.sale_phase_mint_counter.insert(&key, &(minted + amount));
+  }
+
   pub fn internal_sale_add_token(&mut self, sale_id: &SaleId, token_id: &TokenId) {
-    let sale_by_token = self.sale_by_token.as_mut().unwrap();
-    let tokens_per_sale = self.sale_tokens.as_mut().unwrap();
+    self.internal_sale_bind_token(sale_id, token_id);
+
+    if let Some(drop_table) = self.sale_drop_table_by_id.get(sale_id) {
+      let entry = self.internal_sale_drop_entry(&drop_table, token_id).expect("Token does not match the drop table");
+      let mut pools = self.sale_drop_pools.get(sale_id).expect("Not found drop pools");
+
+      pools[entry].push(token_id);
+      self.sale_drop_pools.insert(sale_id, &pools);
+
+      return;
+    }
+
     let random_tokens = self.sale_random_tokens.as_mut().unwrap();
     let mut sale_random = random_tokens.get(&sale_id).unwrap_or_else(|| {
-      vec![]
+      Vector::new(StorageKey::SaleRandomTokensInner {
+        sale_hash: env::sha256(sale_id.as_bytes()),
+      })
     });
 
+    sale_random.push(token_id);
+    random_tokens.insert(sale_id, &sale_random);
+  }
+
+  /// Record the token as a part of the sale without putting it up for sale
+  pub(crate) fn internal_sale_bind_token(&mut self, sale_id: &SaleId, token_id: &TokenId) {
+    let sale_by_token = self.sale_by_token.as_mut().unwrap();
+    let tokens_per_sale = self.sale_tokens.as_mut().unwrap();
+
     let sale_tokens = &mut tokens_per_sale.get(&sale_id).unwrap_or_else(|| {
       UnorderedSet::new(StorageKey::SaleTokensInner {
         sale_hash: env::sha256(sale_id.as_bytes()),
@@ -89,12 +285,21 @@ impl NonFungibleToken {
     sale_tokens.insert(&token_id);
     tokens_per_sale.insert(&sale_id, &sale_tokens);
     sale_by_token.insert(&token_id, &sale_id);
-    sale_random.push(token_id.clone());
-    random_tokens.insert(&sale_id, &sale_random);
 
     assert_ne!(sale_tokens.len(), 0, "{}", &format!("Token does not added to sale {}", &token_id.to_string()));
   }
 
+  pub(crate) fn internal_sale_remove_token(&mut self, sale_id: &SaleId, token_id: &TokenId) {
+    let tokens_per_sale = self.sale_tokens.as_mut().unwrap();
+
+    if let Some(mut sale_tokens) = tokens_per_sale.get(sale_id) {
+      sale_tokens.remove(token_id);
+      tokens_per_sale.insert(sale_id, &sale_tokens);
+    }
+
+    self.sale_by_token.as_mut().unwrap().remove(token_id);
+  }
+
   pub(crate) fn internal_mint_counter_change(&mut self, owner_id: &AccountId, sale_id: &SaleId, value: &u32) {
     if let Some(mint_counter) = &mut self.sale_mint_counter {
       let mut sale_accounts = mint_counter.get(&sale_id).unwrap_or_else(|| {
@@ -117,8 +322,17 @@ impl NonFungibleToken {
     sale_accounts.get(&owner_id).unwrap_or_else(|| 0)
   }
 
-  pub(crate) fn internal_random_tokens(&mut self, sale_id: &SaleId, amount: &u32) -> Vec<TokenId> {
-    let mut random_tokens = self.sale_random_tokens.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+  /// Draw `amount` of not minted tokens and template copies of the sale for the receiver,
+  /// template copies are minted right to the receiver
+  pub(crate) fn internal_random_tokens(&mut self, sale_id: &SaleId, amount: &u32, receiver_id: &AccountId) -> Vec<TokenId> {
+    let mut random_tokens = self.internal_sale_random_tokens(sale_id);
+    let mut templates = self.sale_templates_by_id.get(sale_id).unwrap_or_default();
+    let mut template_copies: u64 = templates.iter().map(|template| template.amount - template.minted).sum();
+
+    let drop_table = self.sale_drop_table_by_id.get(sale_id);
+    let mut drop_pools = self.sale_drop_pools.get(sale_id).unwrap_or_default();
+    let pity_key = (sale_id.clone(), receiver_id.clone());
+    let mut pity = self.sale_drop_pity.get(&pity_key).unwrap_or_default();
 
     let mut index = 0;
     let mut tokens = Vec::new();
@@ -129,40 +343,314 @@ impl NonFungibleToken {
         break;
       }
 
-      let rand_index = rnd.gen_range(0, random_tokens.len().clone());
-      let token_id = random_tokens.get(rand_index).expect("Invalid token index").clone();
-      let owner_id = self.owner_by_id.get(&token_id);
+      if let Some(drop_table) = &drop_table {
+        let token_id = self.internal_sale_drop_draw(drop_table, &mut drop_pools, &mut pity, &mut rnd);
 
-      if let Some(owner_id) = owner_id {
-        if owner_id != self.owner_id {
-          env::panic_str("Token already minted");
-        }
+        self.internal_sale_draw_transfer(&token_id, receiver_id);
+        tokens.push(token_id);
+        index += 1;
+        continue;
       }
 
-      random_tokens.remove(rand_index);
-      self.sale_random_tokens.as_mut().unwrap().insert(&sale_id, &random_tokens);
+      let rand_index = rnd.gen_range(0, random_tokens.len() + template_copies);
 
-      assert_eq!(&self.owner_by_id.get(&token_id).unwrap(), &self.owner_id, "Token already minted");
+      if rand_index < random_tokens.len() {
+        // the last token takes the place of the drawn one, so a draw does not depend on the sale size
+        let token_id = random_tokens.swap_remove(rand_index);
+
+        self.internal_sale_draw_transfer(&token_id, receiver_id);
+        tokens.push(token_id);
+      } else {
+        let copy_index = rand_index - random_tokens.len();
+        let token_id = self.internal_sale_template_mint(sale_id, &mut templates, copy_index, receiver_id, &mut rnd);
+
+        template_copies -= 1;
+        tokens.push(token_id);
+      }
 
-      tokens.push(token_id);
       index = index + 1;
     }
 
+    self.sale_random_tokens.as_mut().unwrap().insert(sale_id, &random_tokens);
+    if !templates.is_empty() {
+      self.sale_templates_by_id.insert(sale_id, &templates);
+    }
+    if drop_table.is_some() {
+      self.sale_drop_pools.insert(sale_id, &drop_pools);
+      self.sale_drop_pity.insert(&pity_key, &pity);
+    }
+
     tokens
   }
 
-  pub(crate) fn internal_random_mint(&mut self, receiver_id: &AccountId, sale_id: &SaleId, amount: &u64) {
+  fn internal_sale_draw_transfer(&mut self, token_id: &TokenId, receiver_id: &AccountId) {
+    let owner_id = self.owner_by_id.get(token_id);
+
+    if let Some(owner_id) = owner_id {
+      if owner_id != self.owner_id {
+        env::panic_str("Token already minted");
+      }
+    }
+
+    assert_eq!(&self.owner_by_id.get(token_id).unwrap(), &self.owner_id, "Token already minted");
+
+    self.internal_transfer_unguarded(token_id, &self.owner_id.clone(), receiver_id);
+  }
+
+  pub(crate) fn internal_sale_drop_pools_init(&mut self, sale_id: &SaleId, entries: u32) {
+    let pools: Vec<Vector<TokenId>> = (0..entries)
+      .map(|entry| {
+        Vector::new(StorageKey::SaleDropPoolInner {
+          sale_hash: env::sha256(sale_id.as_bytes()),
+          entry,
+        })
+      })
+      .collect();
+
+    self.sale_drop_pools.insert(sale_id, &pools);
+  }
+
+  /// Index of the first drop table entry the token matches
+  pub(crate) fn internal_sale_drop_entry(&self, drop_table: &DropTable, token_id: &TokenId) -> Option<usize> {
+    let rarity = self.token_rarity_by_id.as_ref().unwrap().get(token_id);
+    let token_type = self.token_type_by_id.as_ref().unwrap().get(token_id);
+    let token_sub_type = self.token_sub_type_by_id.as_ref().unwrap().get(token_id);
+
+    drop_table.entries.iter().position(|entry| match &entry.key {
+      DropKey::Rarity { rarity: entry_rarity } => rarity.as_ref() == Some(entry_rarity),
+      DropKey::Kind { token_type: entry_type, token_sub_type: entry_sub_type } => {
+        token_type.as_ref() == Some(entry_type) && (entry_sub_type.is_none() || &token_sub_type == entry_sub_type)
+      }
+    })
+  }
+
+  /// Pick an entry by pity or by weight among not empty entries and take a random token of it
+  fn internal_sale_drop_draw(&mut self, drop_table: &DropTable, pools: &mut [Vector<TokenId>], pity: &mut Vec<u32>, rnd: &mut StdRng) -> TokenId {
+    pity.resize(drop_table.pity.len(), 0);
+
+    let forced = drop_table.pity
+      .iter()
+      .enumerate()
+      .find(|(index, rule)| pity[*index] + 1 >= rule.every && !pools[rule.entry as usize].is_empty())
+      .map(|(_, rule)| rule.entry as usize);
+
+    let entry = match forced {
+      Some(entry) => entry,
+      None => {
+        let total_weight: u64 = drop_table.entries
+          .iter()
+          .zip(pools.iter())
+          .filter(|(_, pool)| !pool.is_empty())
+          .map(|(entry, _)| entry.weight as u64)
+          .sum();
+        let mut point = rnd.gen_range(0, total_weight);
+
+        drop_table.entries
+          .iter()
+          .zip(pools.iter())
+          .position(|(entry, pool)| {
+            if pool.is_empty() {
+              return false;
+            }
+            if point < entry.weight as u64 {
+              true
+            } else {
+              point -= entry.weight as u64;
+              false
+            }
+          })
+          .expect("Sale is sold out")
+      }
+    };
+
+    let pool = &mut pools[entry];
+    let token_id = pool.swap_remove(rnd.gen_range(0, pool.len()));
+
+    drop_table.pity.iter().enumerate().for_each(|(index, rule)| {
+      pity[index] = if rule.entry as usize == entry { 0 } else { pity[index] + 1 };
+    });
+
+    token_id
+  }
+
+  /// Mint the copy at `copy_index` of not minted copies of all templates, token id is `sale:template:copy`
+  fn internal_sale_template_mint(&mut self, sale_id: &SaleId, templates: &mut [SaleTemplate], mut copy_index: u64, receiver_id: &AccountId, rnd: &mut StdRng) -> TokenId {
+    let (template_index, template) = templates
+      .iter_mut()
+      .enumerate()
+      .find(|(_, template)| {
+        let rest = template.amount - template.minted;
+
+        if copy_index < rest {
+          true
+        } else {
+          copy_index -= rest;
+          false
+        }
+      })
+      .expect("Not found sale template");
+
+    let prefix = format!("{}:{}", sale_id, template_index);
+    let token_id = self.internal_template_mint(template, &prefix, receiver_id, rnd);
+
+    // the token is not in the sale pool, so it is bound to the sale after it is minted
+    self.internal_sale_bind_token(sale_id, &token_id);
+
+    token_id
+  }
+
+  /// Storage of one minted copy of the template
+  pub(crate) fn internal_template_storage(&self, template: &SaleTemplate) -> StorageUsage {
+    self.extra_storage_in_bytes_per_token + TOKEN_RECORDS_STORAGE + template_storage(template)
+  }
+
+  /// Mint the next copy of the template as `prefix:copy` with a rarity drawn by the template weights
+  pub(crate) fn internal_template_mint(&mut self, template: &mut SaleTemplate, prefix: &str, receiver_id: &AccountId, rnd: &mut StdRng) -> TokenId {
+    template.minted += 1;
+
+    let copy = template.minted.to_string();
+    let token_id = format!("{}:{}", prefix, copy);
+
+    let total_weight: u32 = template.rarity_weights.iter().map(|(_, weight)| weight).sum();
+    let mut point = rnd.gen_range(0, total_weight);
+    let rarity = template.rarity_weights
+      .iter()
+      .find(|(_, weight)| {
+        if point < *weight {
+          true
+        } else {
+          point -= weight;
+          false
+        }
+      })
+      .map(|(rarity, _)| rarity.clone())
+      .unwrap();
+
+    let pattern = |value: &Option<String>| value.as_ref().map(|value| value.replace("{copy}", &copy));
+    let metadata = TokenMetadata {
+      title: pattern(&template.metadata.title),
+      description: pattern(&template.metadata.description),
+      media: pattern(&template.metadata.media),
+      reference: pattern(&template.metadata.reference),
+      copies: template.metadata.copies.or(Some(template.amount)),
+      ..template.metadata.clone()
+    };
+
+    self.internal_create_nft_with_refund(
+      &token_id,
+      Some(receiver_id.clone()),
+      Some(metadata),
+      rarity,
+      template.collection.clone(),
+      None,
+      None,
+      None,
+      None,
+      template.token_type.clone(),
+      template.token_sub_type.clone(),
+      None,
+    );
+
+    token_id
+  }
+
+  /// Not minted tokens and template copies of the sale, including the tokens that are not migrated yet
+  pub(crate) fn internal_sale_not_minted(&self, sale_id: &SaleId) -> Option<u64> {
+    let random_tokens = self.sale_random_tokens.as_ref().unwrap().get(sale_id).map(|tokens| tokens.len());
+    let legacy_tokens = self.sale_random_tokens_legacy.get(sale_id).map(|tokens| tokens.len() as u64);
+    let template_copies = self.sale_templates_by_id
+      .get(sale_id)
+      .map(|templates| templates.iter().map(|template| template.amount - template.minted).sum::<u64>());
+    let drop_tokens = self.sale_drop_pools
+      .get(sale_id)
+      .map(|pools| pools.iter().map(|pool| pool.len()).sum::<u64>());
+
+    let counts = [random_tokens, legacy_tokens, template_copies, drop_tokens];
+
+    if counts.iter().all(Option::is_none) {
+      None
+    } else {
+      Some(counts.iter().map(|count| count.unwrap_or(0)).sum())
+    }
+  }
+
+  pub(crate) fn internal_sale_random_tokens(&self, sale_id: &SaleId) -> Vector<TokenId> {
+    if self.sale_random_tokens_legacy.contains_key(sale_id) {
+      env::panic_str("Sale tokens are not migrated");
+    }
+
+    self.sale_random_tokens.as_ref().unwrap().get(sale_id).unwrap_or_else(|| {
+      Vector::new(StorageKey::SaleRandomTokensInner {
+        sale_hash: env::sha256(sale_id.as_bytes()),
+      })
+    })
+  }
+
+  /// Move up to `limit` tokens of the sale from the legacy storage, returns how many are left there
+  pub(crate) fn internal_sale_migrate_tokens(&mut self, sale_id: &SaleId, limit: Option<u32>) -> u64 {
+    let mut legacy_tokens = self.sale_random_tokens_legacy.get(sale_id).expect("Sale tokens are migrated");
+    let count = std::cmp::min(limit.map(|l| l as usize).unwrap_or(legacy_tokens.len()), legacy_tokens.len());
+    let token_ids = legacy_tokens.split_off(legacy_tokens.len() - count);
+
+    let random_tokens = self.sale_random_tokens.as_mut().unwrap();
+    let mut sale_random = random_tokens.get(sale_id).unwrap_or_else(|| {
+      Vector::new(StorageKey::SaleRandomTokensInner {
+        sale_hash: env::sha256(sale_id.as_bytes()),
+      })
+    });
+    sale_random.extend(token_ids);
+    random_tokens.insert(sale_id, &sale_random);
+
+    if legacy_tokens.is_empty() {
+      self.sale_random_tokens_legacy.remove(sale_id);
+    } else {
+      self.sale_random_tokens_legacy.insert(sale_id, &legacy_tokens);
+    }
+
+    legacy_tokens.len() as u64
+  }
+
+  /// Amount of tokens a purchase of `amount` gets, limited by the not minted tokens of the sale
+  pub(crate) fn internal_sale_fill_amount(&self, sale_id: &SaleId, amount: &u64) -> u64 {
+    std::cmp::min(*amount, self.internal_sale_available(sale_id))
+  }
+
+  /// Tokens of the sale that are neither minted nor reserved
+  pub(crate) fn internal_sale_available(&self, sale_id: &SaleId) -> u64 {
+    let rest_amount = self.internal_sale_not_minted(sale_id).expect("Not found sale");
+
+    rest_amount - self.sale_reserved_by_id.get(sale_id).unwrap_or(0)
+  }
+
+  /// Storage of one revealed token of the sale, paid with the reservation
+  pub(crate) fn internal_sale_token_storage(&self, sale_id: &SaleId) -> StorageUsage {
+    let template_storage = self.sale_templates_by_id
+      .get(sale_id)
+      .and_then(|templates| templates.iter().map(template_storage).max())
+      .unwrap_or(0);
+
+    self.extra_storage_in_bytes_per_token + TOKEN_RECORDS_STORAGE + template_storage
+  }
+
+  /// Take paid slots of the sale, tokens are drawn by `nft_sale_reveal` in a later block
+  pub(crate) fn internal_sale_reserve(&mut self, payer_id: &AccountId, receiver_id: &AccountId, sale_id: &SaleId, amount: &u64, price: Balance, ft_token: Option<AccountId>, proof: &Option<Vec<Base58CryptoHash>>) -> SaleReservation {
     let _amount = amount.clone() as u32;
 
     self.assert_sale_not_locked(&sale_id);
-    self.assert_sale_started(&sale_id);
+    self.assert_sale_not_ended(sale_id);
+
+    let phase = self.internal_sale_current_phase(sale_id);
+    match &phase {
+      Some(phase) => self.internal_sale_phase_use(receiver_id, sale_id, phase, &_amount, proof),
+      None => self.assert_sale_started(sale_id),
+    }
 
     let sale = self.sale_by_id.as_ref().unwrap().get(sale_id).expect("Not found sale");
     let buy_max = sale.buy_max;
     let per_transaction_min = sale.per_transaction_min;
     let per_transaction_max = sale.per_transaction_max;
 
-    let rest_amount = self.sale_random_tokens.as_ref().unwrap().get(&sale_id).expect("Not found sale").len() as u32;
+    let rest_amount = self.internal_sale_available(sale_id) as u32;
     let owner_minted = self.internal_mint_counter_by_sale(&receiver_id, &sale_id);
 
     if _amount > rest_amount {
@@ -172,14 +660,226 @@ impl NonFungibleToken {
     assert!(_amount <= per_transaction_max, "Invalid mint max amount");
     assert!(_amount >= per_transaction_min, "Invalid mint min amount");
 
-    let tokens = self.internal_random_tokens(sale_id, &_amount);
-
-    tokens.iter().for_each(|token_id| {
-      self.internal_transfer_unguarded(&token_id, &self.owner_id.clone(), &receiver_id);
+    let next_minted = owner_minted + _amount;
+    self.internal_mint_counter_change(receiver_id, sale_id, &next_minted);
+    self.internal_sale_reserve_slots(sale_id, *amount);
+
+    let reservation = SaleReservation {
+      id: self.sale_reservations_next_id,
+      sale_id: sale_id.clone(),
+      payer_id: payer_id.clone(),
+      receiver_id: receiver_id.clone(),
+      amount: *amount,
+      price: U128::from(price),
+      ft_token,
+      phase: phase.map(|phase| phase.name),
+      block_height: env::block_height(),
+      storage: self.internal_sale_token_storage(sale_id) * amount,
+    };
+    self.sale_reservations_next_id += 1;
+    self.sale_reservation_by_id.insert(&reservation.id, &reservation);
+
+    let mut account_reservations = self.sale_reservations_per_account.get(receiver_id).unwrap_or_else(|| {
+      UnorderedSet::new(StorageKey::SaleReservationsPerAccount {
+        account_hash: env::sha256(receiver_id.as_bytes()),
+      })
     });
+    account_reservations.insert(&reservation.id);
+    self.sale_reservations_per_account.insert(receiver_id, &account_reservations);
+
+    SaleReserve {
+      reservation: &reservation,
+    }.emit();
+
+    // the callback runs in a later block, its random seed is not known while the slots are paid
+    ext_self::nft_sale_reveal(
+      reservation.id,
+      env::current_account_id(),
+      NO_DEPOSIT,
+      GAS_FOR_SALE_REVEAL + GAS_FOR_SALE_REVEAL_TOKEN * *amount,
+    );
+
+    reservation
+  }
+
+  pub(crate) fn internal_sale_reservation_remove(&mut self, reservation: &SaleReservation) {
+    self.sale_reservation_by_id.remove(&reservation.id);
+
+    if let Some(mut account_reservations) = self.sale_reservations_per_account.get(&reservation.receiver_id) {
+      account_reservations.remove(&reservation.id);
+
+      if account_reservations.is_empty() {
+        self.sale_reservations_per_account.remove(&reservation.receiver_id);
+      } else {
+        self.sale_reservations_per_account.insert(&reservation.receiver_id, &account_reservations);
+      }
+    }
 
-    let next_minted = u32::from(owner_minted + _amount);
-    self.internal_mint_counter_change(&receiver_id, sale_id, &next_minted);
+    self.internal_sale_release_slots(&reservation.sale_id, reservation.amount);
+  }
+
+  pub(crate) fn internal_sale_reserve_slots(&mut self, sale_id: &SaleId, amount: u64) {
+    let reserved = self.sale_reserved_by_id.get(sale_id).unwrap_or(0);
+    self.sale_reserved_by_id.insert(sale_id, &(reserved + amount));
+  }
+
+  pub(crate) fn internal_sale_release_slots(&mut self, sale_id: &SaleId, amount: u64) {
+    let reserved = self.sale_reserved_by_id.get(sale_id).expect("Not found reserved tokens");
+
+    if reserved == amount {
+      self.sale_reserved_by_id.remove(sale_id);
+    } else {
+      self.sale_reserved_by_id.insert(sale_id, &(reserved - amount));
+    }
+  }
+
+  pub(crate) fn internal_sale_reveal(&mut self, reservation_id: &SaleReservationId) -> Vec<TokenId> {
+    let reservation = self.sale_reservation_by_id.get(reservation_id).expect("Not found reservation");
+    let block_height = env::block_height();
+
+    assert!(block_height > reservation.block_height, "Reservation can be revealed only in a later block");
+    assert!(block_height <= reservation.block_height + SALE_REVEAL_TIMEOUT, "Reservation is expired");
+
+    self.internal_sale_reservation_remove(&reservation);
+
+    let initial_storage_usage = env::storage_usage();
+    let tokens = self.internal_random_tokens(&reservation.sale_id, &(reservation.amount as u32), &reservation.receiver_id);
+    self.internal_storage_settle(&reservation.payer_id, reservation.storage, storage_used_since(initial_storage_usage));
+
+    let price = reservation.price.0;
+    self.internal_sale_revenue_add(&reservation.sale_id, reservation.ft_token.clone(), price * (reservation.amount as u128));
+    if reservation.phase.is_none() {
+      self.internal_dutch_auction_purchase(&reservation.sale_id, &reservation.payer_id, &reservation.amount, price);
+    }
+
+    SaleReveal {
+      reservation_id: &reservation.id,
+      sale_id: &reservation.sale_id,
+      receiver_id: &reservation.receiver_id,
+      token_ids: &tokens,
+    }.emit();
+
+    tokens
+  }
+
+  /// Return the payment of a reservation that was not revealed in time and free its slots
+  pub(crate) fn internal_sale_refund(&mut self, reservation_id: &SaleReservationId) -> Balance {
+    let reservation = self.sale_reservation_by_id.get(reservation_id).expect("Not found reservation");
+
+    assert!(env::block_height() > reservation.block_height + SALE_REVEAL_TIMEOUT, "Reservation is not expired");
+
+    self.internal_sale_reservation_remove(&reservation);
+
+    let amount = reservation.amount as u32;
+    let minted = self.internal_mint_counter_by_sale(&reservation.receiver_id, &reservation.sale_id);
+    self.internal_mint_counter_change(&reservation.receiver_id, &reservation.sale_id, &(minted - amount));
+
+    if let Some(phase_name) = &reservation.phase {
+      let key = (reservation.sale_id.clone(), phase_name.clone(), reservation.receiver_id.clone());
+      let phase_minted = self.sale_phase_mint_counter.get(&key).unwrap_or(0);
+      self.sale_phase_mint_counter.insert(&key, &(phase_minted - amount));
+    }
+
+    self.internal_storage_settle(&reservation.payer_id, reservation.storage, 0);
+
+    let refund = reservation.price.0 * (reservation.amount as u128);
+    self.internal_payout_transfer(&reservation.ft_token, &reservation.payer_id, refund, format!("Sale {} refund", reservation.sale_id));
+
+    SaleRefund {
+      reservation_id: &reservation.id,
+      sale_id: &reservation.sale_id,
+      payer_id: &reservation.payer_id,
+      ft_token: reservation.ft_token.as_ref(),
+      amount: &U128::from(refund),
+    }.emit();
+
+    refund
+  }
+
+  pub(crate) fn internal_sale_revenue_add(&mut self, sale_id: &SaleId, ft_token: Option<AccountId>, amount: Balance) {
+    let mut proceeds = self.sale_proceeds_by_id.get(sale_id).unwrap_or_default();
+
+    match proceeds.iter_mut().find(|p| p.ft_token == ft_token) {
+      Some(p) => p.total = U128::from(p.total.0 + amount),
+      None => proceeds.push(SaleProceeds {
+        ft_token: ft_token.clone(),
+        total: U128::from(amount),
+        withdrawn: U128::from(0),
+      }),
+    }
+
+    self.sale_proceeds_by_id.insert(sale_id, &proceeds);
+
+    SaleRevenue {
+      sale_id,
+      ft_token: ft_token.as_ref(),
+      amount: &U128::from(amount),
+    }.emit();
+  }
+
+  /// Split `amount` between sale beneficiaries, the rounding remainder goes to the largest share
+  pub(crate) fn internal_sale_split(&self, sale_id: &SaleId, amount: Balance) -> Vec<(AccountId, Balance)> {
+    let mut beneficiaries: Vec<(AccountId, u32)> = self.sale_beneficiaries_by_id
+      .get(sale_id)
+      .map(|beneficiaries| beneficiaries.into_iter().collect())
+      .unwrap_or_else(|| vec![(self.owner_id.clone(), 10_000)]);
+
+    beneficiaries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut parts: Vec<(AccountId, Balance)> = beneficiaries
+      .into_iter()
+      .map(|(account_id, share)| (account_id, amount * share as u128 / 10_000))
+      .collect();
+
+    let paid: Balance = parts.iter().map(|(_, part)| part).sum();
+    parts[0].1 += amount - paid;
+
+    parts
+  }
+
+  pub(crate) fn internal_sale_payout(&self, sale_id: &SaleId, ft_token: &Option<AccountId>, receiver_id: &AccountId, amount: Balance) {
+    let transfer = transfer_near_or_ft(ft_token, receiver_id, amount, format!("Sale {} proceeds", sale_id));
+
+    transfer.then(ext_self::nft_resolve_sale_withdraw(
+      sale_id.clone(),
+      ft_token.clone(),
+      receiver_id.clone(),
+      U128::from(amount),
+      env::current_account_id(),
+      NO_DEPOSIT,
+      GAS_FOR_RESOLVE_SALE_WITHDRAW,
+    ));
+  }
+
+  pub(crate) fn internal_sale_rebate_payout(&self, sale_id: &SaleId, account_id: &AccountId, count: u64, paid: Balance, amount: Balance) {
+    let ft_token = self.sale_by_ft_token.as_ref().unwrap().get(sale_id);
+    let transfer = transfer_near_or_ft(&ft_token, account_id, amount, format!("Sale {} rebate", sale_id));
+
+    transfer.then(ext_self::nft_resolve_sale_rebate(
+      sale_id.clone(),
+      account_id.clone(),
+      count,
+      U128::from(paid),
+      U128::from(amount),
+      env::current_account_id(),
+      NO_DEPOSIT,
+      GAS_FOR_RESOLVE_SALE_WITHDRAW,
+    ));
+  }
+
+  /// Change total proceeds of the sale currency, used for rebates
+  pub(crate) fn internal_sale_revenue_change(&mut self, sale_id: &SaleId, amount: Balance, add: bool) {
+    let ft_token = self.sale_by_ft_token.as_ref().unwrap().get(sale_id);
+    let mut proceeds = self.sale_proceeds_by_id.get(sale_id).expect("Not found sale proceeds");
+    let p = proceeds.iter_mut().find(|p| p.ft_token == ft_token).expect("Not found sale proceeds");
+
+    p.total = if add {
+      U128::from(p.total.0 + amount)
+    } else {
+      U128::from(p.total.0 - amount)
+    };
+
+    self.sale_proceeds_by_id.insert(sale_id, &proceeds);
   }
 
   // pub fn internal_nft_sale_burn(&mut self, sale_id: &SaleId) {
@@ -193,3 +893,8 @@ impl NonFungibleToken {
   //   self.sale_date_by_id.as_mut().unwrap().remove(sale_id);
   // }
 }
+
+/// Metadata bytes of a template copy, `{copy}` grows up to 20 digits in each of the patterned fields
+pub(crate) fn template_storage(template: &SaleTemplate) -> StorageUsage {
+  template.metadata.try_to_vec().unwrap().len() as StorageUsage + 4 * 20
+}
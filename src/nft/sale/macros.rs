@@ -4,6 +4,7 @@
 macro_rules! impl_non_fungible_token_sales {
     ($contract: ident, $tokens: ident) => {
         use $crate::{SaleCore, SaleEnumeration, SaleId, JsonSale};
+        use $crate::nft::sale::{SaleTreasury, SaleResolver, SaleProceeds, SalePhases, SalePhase, SaleSettle, SaleDutchAuction, DutchAuction, SaleReveal, SaleReservation, SaleReservationId, SaleTemplates, SaleTemplate, SaleDropTables, DropTable, DropOdds};
 
         #[near_bindgen]
         impl SaleCore for $contract {
@@ -11,24 +12,169 @@ macro_rules! impl_non_fungible_token_sales {
             self.$tokens.nft_sale_add(id, name, amount, price, per_transaction_min, per_transaction_max, buy_max, ft_token)
           }
 
-          fn nft_sale_start(&mut self, sale_id: SaleId, date: u64) -> JsonSale {
-            self.$tokens.nft_sale_start(sale_id, date)
+          fn nft_sale_start(&mut self, sale_id: SaleId, date: u64, end_date: Option<u64>) -> JsonSale {
+            self.$tokens.nft_sale_start(sale_id, date, end_date)
           }
 
-          fn nft_sale_update(&mut self, sale_id: SaleId, date: u64, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32) -> JsonSale {
-            self.$tokens.nft_sale_update(sale_id, date, per_transaction_min, per_transaction_max, buy_max)
+          fn nft_sale_update(&mut self, sale_id: SaleId, date: u64, end_date: Option<u64>, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32) -> JsonSale {
+            self.$tokens.nft_sale_update(sale_id, date, end_date, per_transaction_min, per_transaction_max, buy_max)
           }
 
            fn nft_sale_pause(&mut self, sale_id: SaleId, pause: bool) -> JsonSale {
             self.$tokens.nft_sale_pause(sale_id, pause)
           }
 
+          fn nft_sale_settle(&mut self, sale_id: SaleId, settle: SaleSettle, limit: Option<u32>) -> JsonSale {
+            self.$tokens.nft_sale_settle(sale_id, settle, limit)
+          }
+
+          fn nft_sale_migrate_tokens(&mut self, sale_id: SaleId, limit: Option<u32>) -> u64 {
+            self.$tokens.nft_sale_migrate_tokens(sale_id, limit)
+          }
+
              #[payable]
-            fn nft_buy(&mut self, receiver_id: AccountId, sale_id: SaleId, amount: u64) {
-              self.$tokens.nft_buy(receiver_id, sale_id, amount)
+            fn nft_buy(&mut self, receiver_id: AccountId, sale_id: SaleId, amount: u64, proof: Option<Vec<near_sdk::json_types::Base58CryptoHash>>) -> SaleReservation {
+              self.$tokens.nft_buy(receiver_id, sale_id, amount, proof)
             }
         }
 
+        #[near_bindgen]
+        impl SaleTemplates for $contract {
+          fn nft_sale_add_template(&mut self, sale_id: SaleId, template: SaleTemplate) -> Vec<SaleTemplate> {
+            self.$tokens.nft_sale_add_template(sale_id, template)
+          }
+
+          fn nft_sale_templates(&self, sale_id: SaleId) -> Vec<SaleTemplate> {
+            self.$tokens.nft_sale_templates(sale_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl SaleDropTables for $contract {
+          fn nft_sale_set_drop_table(&mut self, sale_id: SaleId, drop_table: Option<DropTable>) -> Option<DropTable> {
+            self.$tokens.nft_sale_set_drop_table(sale_id, drop_table)
+          }
+
+          fn nft_sale_drop_table(&self, sale_id: SaleId) -> Option<DropTable> {
+            self.$tokens.nft_sale_drop_table(sale_id)
+          }
+
+          fn nft_sale_drop_odds(&self, sale_id: SaleId) -> Vec<DropOdds> {
+            self.$tokens.nft_sale_drop_odds(sale_id)
+          }
+
+          fn nft_sale_drop_pity(&self, sale_id: SaleId, account_id: AccountId) -> Vec<u32> {
+            self.$tokens.nft_sale_drop_pity(sale_id, account_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl SaleReveal for $contract {
+          #[private]
+          fn nft_sale_reveal(&mut self, reservation_id: SaleReservationId) -> Vec<TokenId> {
+            self.$tokens.nft_sale_reveal(reservation_id)
+          }
+
+          fn nft_sale_refund(&mut self, reservation_id: SaleReservationId) -> U128 {
+            self.$tokens.nft_sale_refund(reservation_id)
+          }
+
+          fn nft_sale_reservation(&self, reservation_id: SaleReservationId) -> Option<SaleReservation> {
+            self.$tokens.nft_sale_reservation(reservation_id)
+          }
+
+          fn nft_sale_reservations(&self, account_id: AccountId) -> Vec<SaleReservation> {
+            self.$tokens.nft_sale_reservations(account_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl SalePhases for $contract {
+          fn nft_sale_set_phases(&mut self, sale_id: SaleId, phases: Vec<SalePhase>) -> Vec<SalePhase> {
+            self.$tokens.nft_sale_set_phases(sale_id, phases)
+          }
+
+          fn nft_sale_phases(&self, sale_id: SaleId) -> Vec<SalePhase> {
+            self.$tokens.nft_sale_phases(sale_id)
+          }
+
+          fn nft_sale_current_phase(&self, sale_id: SaleId) -> Option<SalePhase> {
+            self.$tokens.nft_sale_current_phase(sale_id)
+          }
+
+          fn nft_sale_phase_minted(&self, sale_id: SaleId, phase_name: String, account_id: AccountId) -> u32 {
+            self.$tokens.nft_sale_phase_minted(sale_id, phase_name, account_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl SaleTreasury for $contract {
+          fn nft_sale_set_beneficiaries(&mut self, sale_id: SaleId, beneficiaries: std::collections::HashMap<AccountId, u32>) {
+            self.$tokens.nft_sale_set_beneficiaries(sale_id, beneficiaries)
+          }
+
+          fn nft_sale_withdraw(&mut self, sale_id: SaleId) -> Vec<SaleProceeds> {
+            self.$tokens.nft_sale_withdraw(sale_id)
+          }
+
+          fn nft_sale_revenue(&self, sale_id: SaleId) -> Vec<SaleProceeds> {
+            self.$tokens.nft_sale_revenue(sale_id)
+          }
+
+          fn nft_sale_beneficiaries(&self, sale_id: SaleId) -> std::collections::HashMap<AccountId, u32> {
+            self.$tokens.nft_sale_beneficiaries(sale_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl SaleDutchAuction for $contract {
+          fn nft_sale_set_dutch_auction(&mut self, sale_id: SaleId, auction: Option<DutchAuction>) -> Option<DutchAuction> {
+            self.$tokens.nft_sale_set_dutch_auction(sale_id, auction)
+          }
+
+          fn nft_sale_dutch_auction(&self, sale_id: SaleId) -> Option<DutchAuction> {
+            self.$tokens.nft_sale_dutch_auction(sale_id)
+          }
+
+          fn nft_sale_current_price(&self, sale_id: SaleId) -> U128 {
+            self.$tokens.nft_sale_current_price(sale_id)
+          }
+
+          fn nft_sale_rebate(&self, sale_id: SaleId, account_id: AccountId) -> U128 {
+            self.$tokens.nft_sale_rebate(sale_id, account_id)
+          }
+
+          fn nft_sale_claim_rebate(&mut self, sale_id: SaleId) -> U128 {
+            self.$tokens.nft_sale_claim_rebate(sale_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl SaleResolver for $contract {
+          #[private]
+          fn nft_resolve_sale_withdraw(
+            &mut self,
+            sale_id: SaleId,
+            ft_token: Option<AccountId>,
+            receiver_id: AccountId,
+            amount: U128,
+          ) -> U128 {
+            self.$tokens.nft_resolve_sale_withdraw(sale_id, ft_token, receiver_id, amount)
+          }
+
+          #[private]
+          fn nft_resolve_sale_rebate(
+            &mut self,
+            sale_id: SaleId,
+            account_id: AccountId,
+            count: u64,
+            paid: U128,
+            amount: U128,
+          ) -> U128 {
+            self.$tokens.nft_resolve_sale_rebate(sale_id, account_id, count, paid, amount)
+          }
+        }
+
           #[near_bindgen]
         impl SaleEnumeration for $contract {
           fn nft_sale_tokens(&self, sale_id: SaleId, from_index: Option<near_sdk::json_types::U128>, limit: Option<u64>) -> Vec<Token> {
@@ -1,6 +1,8 @@
 use near_sdk::serde::{Deserialize, Serialize};
 use crate::*;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::Base58CryptoHash;
+use crate::nft::metadata::{TokenMetadata, TokenRarity, TokenCollection, TokenType, TokenSubType};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
@@ -11,6 +13,7 @@ pub struct Sale {
   pub buy_max: u32,
   pub per_transaction_min: u32,
   pub per_transaction_max: u32,
+  pub end_date: Option<u64>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -26,7 +29,135 @@ pub struct JsonSale {
   pub not_minted: u64,
   pub locked: bool,
   pub start_date: Option<u64>,
+  pub end_date: Option<u64>,
   pub ft_token: Option<AccountId>,
+  pub current_phase: Option<SalePhase>,
+}
+
+/// Presale round, only accounts from the merkle tree of `merkle_root` can buy when it is set
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SalePhase {
+  pub name: String,
+  pub start_date: u64,
+  pub end_date: u64,
+  pub price: U128,
+  pub buy_max: u32,
+  pub merkle_root: Option<Base58CryptoHash>,
+}
+
+/// Proceeds of a sale in one currency, `ft_token` is `None` for NEAR
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleProceeds {
+  pub ft_token: Option<AccountId>,
+  pub total: U128,
+  pub withdrawn: U128,
+}
+
+/// Price drops by `step` every `interval` nanoseconds after the sale start until it reaches `floor_price`.
+/// With `rebate` buyers can claim back what they paid above the final clearing price.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DutchAuction {
+  pub start_price: U128,
+  pub floor_price: U128,
+  pub interval: u64,
+  pub step: U128,
+  pub rebate: bool,
+}
+
+/// Not claimed rebate purchases of a dutch auction, `clearing_price` is the lowest purchase price
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Default)]
+pub struct DutchAuctionTotals {
+  pub sold: u64,
+  pub paid: Balance,
+  pub clearing_price: Balance,
+}
+
+/// What happens to not sold tokens of an ended sale
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SaleSettle {
+  Burn,
+  Move { sale_id: SaleId },
+  // tokens stay with the contract owner outside of any sale
+  Release,
+}
+
+/// Paid slots of a sale, tokens are drawn later with the random seed of a block after `block_height`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleReservation {
+  pub id: SaleReservationId,
+  pub sale_id: SaleId,
+  pub payer_id: AccountId,
+  pub receiver_id: AccountId,
+  pub amount: u64,
+  // price of one token
+  pub price: U128,
+  pub ft_token: Option<AccountId>,
+  pub phase: Option<String>,
+  pub block_height: u64,
+  // bytes paid for the revealed tokens, the unused part is refunded at reveal
+  pub storage: u64,
+}
+
+/// Sale tokens that are minted only when bought, `{copy}` in title, description, media and reference
+/// of the metadata is replaced by the copy number. Rarity of every copy is drawn by `rarity_weights`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleTemplate {
+  pub metadata: TokenMetadata,
+  pub rarity_weights: Vec<(TokenRarity, u32)>,
+  pub collection: TokenCollection,
+  pub token_type: TokenType,
+  pub token_sub_type: Option<TokenSubType>,
+  pub amount: u64,
+  #[serde(default)]
+  pub minted: u64,
+}
+
+/// Drop table entry match, an entry of a kind without `token_sub_type` matches all sub types
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum DropKey {
+  Rarity { rarity: TokenRarity },
+  Kind { token_type: TokenType, token_sub_type: Option<TokenSubType> },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropEntry {
+  pub key: DropKey,
+  pub weight: u32,
+}
+
+/// An account gets a token of the entry at index `entry` at least once in `every` draws of the sale
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropPity {
+  pub entry: u32,
+  pub every: u32,
+}
+
+/// Draws pick an entry by weight first and then a token of the entry, a token belongs to the first entry it matches
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropTable {
+  pub entries: Vec<DropEntry>,
+  pub pity: Vec<DropPity>,
+}
+
+/// Chance of the next draw to get a token of the entry in basis points, without pity
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropOdds {
+  pub key: DropKey,
+  pub weight: u32,
+  pub remaining: u64,
+  pub odds: u32,
 }
 
 pub type SaleId = String;
+pub type SaleReservationId = u64;
@@ -1,8 +1,9 @@
 pub use sale_impl::*;
 pub use metadata::*;
 use crate::nft::{TokenId, Token};
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{U128, Base58CryptoHash};
 use near_sdk::AccountId;
+use std::collections::HashMap;
 
 mod internal;
 pub mod metadata;
@@ -12,16 +13,116 @@ mod macros;
 pub trait SaleCore {
   fn nft_sale_add(&mut self, id: String, name: String, amount: u64, price: U128, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32, ft_token: Option<AccountId>) -> JsonSale;
 
-  fn nft_sale_start(&mut self, sale_id: SaleId, date: u64) -> JsonSale;
+  fn nft_sale_start(&mut self, sale_id: SaleId, date: u64, end_date: Option<u64>) -> JsonSale;
 
-  fn nft_sale_update(&mut self, sale_id: SaleId, date: u64, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32) -> JsonSale;
+  fn nft_sale_update(&mut self, sale_id: SaleId, date: u64, end_date: Option<u64>, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32) -> JsonSale;
 
   fn nft_sale_pause(&mut self, sale_id: SaleId, pause: bool) -> JsonSale;
 
+  /// Settle up to `limit` not sold tokens of an ended sale, can be called until nothing is left
+  fn nft_sale_settle(&mut self, sale_id: SaleId, settle: SaleSettle, limit: Option<u32>) -> JsonSale;
+
+  /// Move up to `limit` not minted tokens stored before the indexed storage, the sale can be bought
+  /// again when it returns 0
+  fn nft_sale_migrate_tokens(&mut self, sale_id: SaleId, limit: Option<u32>) -> u64;
+
+  /// Pay for `amount` tokens of the sale, they are drawn and transferred by `nft_sale_reveal`
   fn nft_buy(
     &mut self,
-    receiver_id: AccountId, sale_id: SaleId, amount: u64
-  );
+    receiver_id: AccountId, sale_id: SaleId, amount: u64, proof: Option<Vec<Base58CryptoHash>>
+  ) -> SaleReservation;
+}
+
+/// Templates are minted only when bought, their copies count in the sale amount and are drawn
+/// together with the minted tokens of the sale
+pub trait SaleTemplates {
+  fn nft_sale_add_template(&mut self, sale_id: SaleId, template: SaleTemplate) -> Vec<SaleTemplate>;
+
+  fn nft_sale_templates(&self, sale_id: SaleId) -> Vec<SaleTemplate>;
+}
+
+/// Drop table has to be set before tokens are added to the sale, tokens that match no entry cannot be added
+pub trait SaleDropTables {
+  fn nft_sale_set_drop_table(&mut self, sale_id: SaleId, drop_table: Option<DropTable>) -> Option<DropTable>;
+
+  fn nft_sale_drop_table(&self, sale_id: SaleId) -> Option<DropTable>;
+
+  fn nft_sale_drop_odds(&self, sale_id: SaleId) -> Vec<DropOdds>;
+
+  /// Draws of the account since it got a token of every pity rule entry
+  fn nft_sale_drop_pity(&self, sale_id: SaleId, account_id: AccountId) -> Vec<u32>;
+}
+
+/// Sale tokens are drawn in a later block than the payment, so the outcome cannot be simulated in advance.
+/// The reveal is scheduled by the purchase itself and only the contract can call it, if it fails the reservation is refunded after it expires.
+pub trait SaleReveal {
+  fn nft_sale_reveal(&mut self, reservation_id: SaleReservationId) -> Vec<TokenId>;
+
+  /// Refund an expired reservation to the payer and release its slots
+  fn nft_sale_refund(&mut self, reservation_id: SaleReservationId) -> U128;
+
+  fn nft_sale_reservation(&self, reservation_id: SaleReservationId) -> Option<SaleReservation>;
+
+  fn nft_sale_reservations(&self, account_id: AccountId) -> Vec<SaleReservation>;
+}
+
+/// Phases go before the public sale, the sale own price and limits apply when no phase is active
+pub trait SalePhases {
+  fn nft_sale_set_phases(&mut self, sale_id: SaleId, phases: Vec<SalePhase>) -> Vec<SalePhase>;
+
+  fn nft_sale_phases(&self, sale_id: SaleId) -> Vec<SalePhase>;
+
+  fn nft_sale_current_phase(&self, sale_id: SaleId) -> Option<SalePhase>;
+
+  fn nft_sale_phase_minted(&self, sale_id: SaleId, phase_name: String, account_id: AccountId) -> u32;
+}
+
+/// Sale proceeds are split between beneficiaries in basis points,
+/// the contract owner gets everything when no beneficiaries are set.
+pub trait SaleTreasury {
+  fn nft_sale_set_beneficiaries(&mut self, sale_id: SaleId, beneficiaries: HashMap<AccountId, u32>);
+
+  /// Pay not withdrawn proceeds of the sale to beneficiaries
+  fn nft_sale_withdraw(&mut self, sale_id: SaleId) -> Vec<SaleProceeds>;
+
+  fn nft_sale_revenue(&self, sale_id: SaleId) -> Vec<SaleProceeds>;
+
+  fn nft_sale_beneficiaries(&self, sale_id: SaleId) -> HashMap<AccountId, u32>;
+}
+
+/// Dutch auction replaces the fixed sale price when set, phase prices still take precedence
+pub trait SaleDutchAuction {
+  fn nft_sale_set_dutch_auction(&mut self, sale_id: SaleId, auction: Option<DutchAuction>) -> Option<DutchAuction>;
+
+  fn nft_sale_dutch_auction(&self, sale_id: SaleId) -> Option<DutchAuction>;
+
+  fn nft_sale_current_price(&self, sale_id: SaleId) -> U128;
+
+  /// Rebate of the account, available once the auction is sold out or ended
+  fn nft_sale_rebate(&self, sale_id: SaleId, account_id: AccountId) -> U128;
+
+  fn nft_sale_claim_rebate(&mut self, sale_id: SaleId) -> U128;
+}
+
+pub trait SaleResolver {
+  /// Returns the withdrawn amount, credits it back to the sale if the transfer failed
+  fn nft_resolve_sale_withdraw(
+    &mut self,
+    sale_id: SaleId,
+    ft_token: Option<AccountId>,
+    receiver_id: AccountId,
+    amount: U128,
+  ) -> U128;
+
+  /// Returns the claimed rebate, restores the purchase record if the transfer failed
+  fn nft_resolve_sale_rebate(
+    &mut self,
+    sale_id: SaleId,
+    account_id: AccountId,
+    count: u64,
+    paid: U128,
+    amount: U128,
+  ) -> U128;
 }
 
 pub trait SaleEnumeration {
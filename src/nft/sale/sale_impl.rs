@@ -1,10 +1,13 @@
-use near_sdk::{env, require, AccountId};
-use near_sdk::json_types::{U128};
+use near_sdk::{env, require, AccountId, PromiseResult};
+use near_sdk::json_types::{U128, Base58CryptoHash};
+use std::collections::HashMap;
 use crate::{TokenId, NonFungibleToken, Token};
 use crate::{SaleId, Sale, SaleCore, SaleEnumeration};
+use crate::nft::sale::{SaleTreasury, SaleResolver, SaleProceeds, SalePhases, SalePhase, SaleSettle, SaleDutchAuction, DutchAuction, SaleReveal, SaleReservation, SaleReservationId, SaleTemplates, SaleTemplate, SaleDropTables, DropTable, DropOdds};
 use crate::nft::base::NonFungibleTokenCore;
-use crate::nft::JsonSale;
-use crate::nft::events_171_mf::{SaleCreate, SaleStart, SaleUpdate, SalePause};
+use crate::nft::{JsonSale, storage_used_since};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::events_171_mf::{SaleCreate, SaleStart, SaleUpdate, SalePause, SaleWithdraw, SalePhasesUpdate, SaleEnd, SaleTemplateAdd, SaleDropTableUpdate};
 
 impl SaleCore for NonFungibleToken {
   fn nft_sale_add(&mut self, id: String, name: String, amount: u64, price: U128, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32, ft_token: Option<AccountId>) -> JsonSale {
@@ -19,10 +22,12 @@ impl SaleCore for NonFungibleToken {
       per_transaction_min,
       per_transaction_max,
       buy_max,
+      end_date: None,
     };
     assert!(self.sale_by_id.as_mut().unwrap().insert(id.clone(), sale).is_none(), "Token type exists");
 
     if let Some(ft_token) = ft_token {
+      self.ft_whitelist.assert_whitelisted(&FtPurpose::SaleMint, &ft_token);
       self.sale_by_ft_token.as_mut().unwrap().insert(&id, &ft_token);
     }
 
@@ -35,16 +40,25 @@ impl SaleCore for NonFungibleToken {
     json_sale
   }
 
-  fn nft_sale_start(&mut self, sale_id: SaleId, date: u64) -> JsonSale {
+  fn nft_sale_start(&mut self, sale_id: SaleId, date: u64, end_date: Option<u64>) -> JsonSale {
     self.assert_owner();
 
-    let sale = self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+    let sale = self.sale_by_id.as_mut().unwrap().get_mut(&sale_id).expect("Not found sale");
+
+    if let Some(end_date) = end_date {
+      assert!(date < end_date, "Sale must end after start");
+    }
+    sale.end_date = end_date;
 
     assert!(self.sales_locked.as_ref().unwrap().contains(&sale_id), "Sale already unlocked");
 
-    let tokens_per_sale = self.sale_tokens.as_ref().unwrap().get(&sale_id).expect("Not found sale tokens").len();
+    let tokens_per_sale = self.sale_tokens.as_ref().unwrap().get(&sale_id).map(|tokens| tokens.len()).unwrap_or(0);
+    let template_copies: u64 = self.sale_templates_by_id
+      .get(&sale_id)
+      .map(|templates| templates.iter().map(|template| template.amount).sum())
+      .unwrap_or(0);
 
-    assert_eq!(u64::from(sale.amount), tokens_per_sale, "Not all of tokens are minted");
+    assert_eq!(sale.amount, tokens_per_sale + template_copies, "Not all of tokens are minted");
 
     self.sales_available.as_mut().unwrap().insert(&sale_id);
     // self.sale_random_tokens.as_mut().unwrap().insert(&sale_id, &tokens_per_sale);
@@ -53,13 +67,14 @@ impl SaleCore for NonFungibleToken {
 
     SaleStart {
       sale_id: &sale_id,
-      date: &date
+      date: &date,
+      end_date: end_date.as_ref(),
     }.emit();
 
     self.enum_get_sale(&sale_id)
   }
 
-  fn nft_sale_update(&mut self, sale_id: SaleId, date: u64, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32) -> JsonSale {
+  fn nft_sale_update(&mut self, sale_id: SaleId, date: u64, end_date: Option<u64>, per_transaction_min: u32, per_transaction_max: u32, buy_max: u32) -> JsonSale {
     self.assert_owner();
     self.assert_sale_not_locked(&sale_id);
 
@@ -75,13 +90,19 @@ impl SaleCore for NonFungibleToken {
       buy_max: buy_max.clone(),
       per_transaction_min: per_transaction_min.clone(),
       per_transaction_max: per_transaction_max.clone(),
+      end_date,
     };
 
+    if let Some(end_date) = end_date {
+      assert!(date < end_date, "Sale must end after start");
+    }
+
     sales.insert(sale_id.clone(), new_sale);
 
     SaleUpdate {
       sale_id: &sale_id,
       date: &date,
+      end_date: end_date.as_ref(),
       per_transaction_min: &per_transaction_min,
       per_transaction_max: &per_transaction_max,
       buy_max: &buy_max,
@@ -107,7 +128,110 @@ impl SaleCore for NonFungibleToken {
     sale
   }
 
-  fn nft_buy(&mut self, receiver_id: AccountId, sale_id: SaleId, amount: u64) {
+  fn nft_sale_settle(&mut self, sale_id: SaleId, settle: SaleSettle, limit: Option<u32>) -> JsonSale {
+    self.assert_owner();
+    self.assert_sale_ended(&sale_id);
+
+    // reserved tokens stay in the sale until they are revealed or refunded
+    let mut random_tokens = self.internal_sale_random_tokens(&sale_id);
+    let mut available = self.internal_sale_available(&sale_id);
+    // the limit counts template copies and tokens together
+    let mut rest = std::cmp::min(limit.map(u64::from).unwrap_or(available), available);
+
+    // template copies go first, nothing is minted for them
+    let mut templates = self.sale_templates_by_id.get(&sale_id).unwrap_or_default();
+    let mut settled_templates = Vec::new();
+
+    for template in templates.iter_mut().rev() {
+      let count = std::cmp::min(template.amount - template.minted, rest);
+
+      if count > 0 {
+        template.amount -= count;
+        available -= count;
+        rest -= count;
+        settled_templates.push(SaleTemplate { amount: count, minted: 0, ..template.clone() });
+      }
+    }
+    if !templates.is_empty() {
+      self.sale_templates_by_id.insert(&sale_id, &templates);
+    }
+
+    let count = std::cmp::min(rest, available);
+    let token_ids: Vec<TokenId> = match self.sale_drop_pools.get(&sale_id) {
+      Some(mut pools) => {
+        let mut token_ids = Vec::new();
+
+        for pool in pools.iter_mut() {
+          while (token_ids.len() as u64) < count {
+            match pool.pop() {
+              Some(token_id) => token_ids.push(token_id),
+              None => break,
+            }
+          }
+        }
+        self.sale_drop_pools.insert(&sale_id, &pools);
+
+        token_ids
+      }
+      None => (0..count).map(|_| random_tokens.pop().unwrap()).collect(),
+    };
+
+    self.sale_random_tokens.as_mut().unwrap().insert(&sale_id, &random_tokens);
+
+    if self.internal_sale_not_minted(&sale_id) == Some(0) {
+      self.sales_available.as_mut().unwrap().remove(&sale_id);
+    }
+
+    match &settle {
+      SaleSettle::Burn => {
+        let owner_id = self.owner_id.clone();
+
+        token_ids.iter().for_each(|token_id| {
+          self.internal_sale_remove_token(&sale_id, token_id);
+          self.internal_burn_token_unguarded(&owner_id, token_id);
+        });
+      }
+      SaleSettle::Move { sale_id: next_sale_id } => {
+        assert_ne!(&sale_id, next_sale_id, "Cannot move tokens to the same sale");
+        assert!(self.sales_locked.as_ref().unwrap().contains(next_sale_id), "Tokens can be moved only to a not started sale");
+
+        let next_sale = self.sale_by_id.as_mut().unwrap().get_mut(next_sale_id).expect("Not found sale");
+        next_sale.amount += token_ids.len() as u64 + settled_templates.iter().map(|template| template.amount).sum::<u64>();
+
+        if !settled_templates.is_empty() {
+          let mut next_templates = self.sale_templates_by_id.get(next_sale_id).unwrap_or_default();
+          next_templates.extend(settled_templates);
+          self.sale_templates_by_id.insert(next_sale_id, &next_templates);
+        }
+
+        token_ids.iter().for_each(|token_id| {
+          self.internal_sale_remove_token(&sale_id, token_id);
+          self.internal_sale_add_token(next_sale_id, token_id);
+        });
+      }
+      SaleSettle::Release => {
+        token_ids.iter().for_each(|token_id| {
+          self.internal_sale_remove_token(&sale_id, token_id);
+        });
+      }
+    }
+
+    SaleEnd {
+      sale_id: &sale_id,
+      settle: &settle,
+      token_ids: &token_ids,
+    }.emit();
+
+    self.enum_get_sale(&sale_id)
+  }
+
+  fn nft_sale_migrate_tokens(&mut self, sale_id: SaleId, limit: Option<u32>) -> u64 {
+    self.assert_owner();
+
+    self.internal_sale_migrate_tokens(&sale_id, limit)
+  }
+
+  fn nft_buy(&mut self, receiver_id: AccountId, sale_id: SaleId, amount: u64, proof: Option<Vec<Base58CryptoHash>>) -> SaleReservation {
     let is_available = self.sales_available.as_ref().unwrap().contains(&sale_id);
 
     if !is_available {
@@ -118,13 +242,394 @@ impl SaleCore for NonFungibleToken {
       env::panic_str("Sale only by FT");
     }
 
-    let sale = self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+    let sale_price = self.internal_sale_price(&sale_id);
     let deposit = env::attached_deposit();
-    let price = sale.price.0;
 
-    assert!(deposit >= price * (amount as u128), "Invalid attached deposit");
+    assert!(deposit >= sale_price * (amount as u128), "Invalid attached deposit");
+
+    // mint what is left and pay only for it, the rest of deposit is refunded
+    let amount = self.internal_sale_fill_amount(&sale_id, &amount);
+    let price = sale_price * (amount as u128);
+    let initial_storage_usage = env::storage_usage();
+
+    let reservation = self.internal_sale_reserve(&env::predecessor_account_id(), &receiver_id, &sale_id, &amount, sale_price, None, &proof);
 
-    self.internal_random_mint(&receiver_id, &sale_id, &amount)
+    let storage_used = storage_used_since(initial_storage_usage) + reservation.storage;
+    self.internal_storage_charge(&env::predecessor_account_id(), storage_used, price);
+
+    reservation
+  }
+}
+
+impl SaleTemplates for NonFungibleToken {
+  fn nft_sale_add_template(&mut self, sale_id: SaleId, template: SaleTemplate) -> Vec<SaleTemplate> {
+    self.assert_owner();
+
+    self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+    assert!(self.sales_locked.as_ref().unwrap().contains(&sale_id), "Templates can be added only to a not started sale");
+    assert!(!self.sale_drop_table_by_id.contains_key(&sale_id), "Sale with a drop table cannot have templates");
+    assert!(template.amount > 0, "Template amount must be greater than 0");
+    assert!(template.rarity_weights.iter().map(|(_, weight)| weight).sum::<u32>() > 0, "Template requires rarity weights");
+    template.metadata.assert_valid();
+
+    let template = SaleTemplate { minted: 0, ..template };
+    let mut templates = self.sale_templates_by_id.get(&sale_id).unwrap_or_default();
+    templates.push(template);
+    self.sale_templates_by_id.insert(&sale_id, &templates);
+
+    SaleTemplateAdd {
+      sale_id: &sale_id,
+      template: templates.last().unwrap(),
+    }.emit();
+
+    templates
+  }
+
+  fn nft_sale_templates(&self, sale_id: SaleId) -> Vec<SaleTemplate> {
+    self.sale_templates_by_id.get(&sale_id).unwrap_or_default()
+  }
+}
+
+impl SaleDropTables for NonFungibleToken {
+  fn nft_sale_set_drop_table(&mut self, sale_id: SaleId, drop_table: Option<DropTable>) -> Option<DropTable> {
+    self.assert_owner();
+
+    self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+    assert!(self.sales_locked.as_ref().unwrap().contains(&sale_id), "Drop table can be set only for a not started sale");
+    assert!(
+      self.sale_tokens.as_ref().unwrap().get(&sale_id).map(|tokens| tokens.is_empty()).unwrap_or(true),
+      "Drop table must be set before sale tokens are minted"
+    );
+    assert!(!self.sale_templates_by_id.contains_key(&sale_id), "Sale with templates cannot have a drop table");
+
+    match &drop_table {
+      Some(drop_table) => {
+        assert!(!drop_table.entries.is_empty(), "Drop table requires entries");
+        assert!(drop_table.entries.iter().all(|entry| entry.weight > 0), "Drop table weight must be greater than 0");
+        drop_table.pity.iter().for_each(|rule| {
+          assert!((rule.entry as usize) < drop_table.entries.len(), "Not found drop table entry {}", rule.entry);
+          assert!(rule.every > 0, "Pity must be greater than 0");
+        });
+
+        self.sale_drop_table_by_id.insert(&sale_id, drop_table);
+        self.internal_sale_drop_pools_init(&sale_id, drop_table.entries.len() as u32);
+      }
+      None => {
+        self.sale_drop_table_by_id.remove(&sale_id);
+        self.sale_drop_pools.remove(&sale_id);
+      }
+    }
+
+    SaleDropTableUpdate {
+      sale_id: &sale_id,
+      drop_table: drop_table.as_ref(),
+    }.emit();
+
+    drop_table
+  }
+
+  fn nft_sale_drop_table(&self, sale_id: SaleId) -> Option<DropTable> {
+    self.sale_drop_table_by_id.get(&sale_id)
+  }
+
+  fn nft_sale_drop_odds(&self, sale_id: SaleId) -> Vec<DropOdds> {
+    let drop_table = match self.sale_drop_table_by_id.get(&sale_id) {
+      Some(drop_table) => drop_table,
+      None => return vec![],
+    };
+    let pools = self.sale_drop_pools.get(&sale_id).expect("Not found drop pools");
+
+    let total_weight: u64 = drop_table.entries
+      .iter()
+      .zip(pools.iter())
+      .filter(|(_, pool)| !pool.is_empty())
+      .map(|(entry, _)| entry.weight as u64)
+      .sum();
+
+    drop_table.entries
+      .into_iter()
+      .zip(pools.iter())
+      .map(|(entry, pool)| {
+        let odds = if pool.is_empty() || total_weight == 0 {
+          0
+        } else {
+          (entry.weight as u64 * 10_000 / total_weight) as u32
+        };
+
+        DropOdds {
+          key: entry.key,
+          weight: entry.weight,
+          remaining: pool.len(),
+          odds,
+        }
+      })
+      .collect()
+  }
+
+  fn nft_sale_drop_pity(&self, sale_id: SaleId, account_id: AccountId) -> Vec<u32> {
+    let drop_table = self.sale_drop_table_by_id.get(&sale_id).expect("Not found drop table");
+    let mut pity = self.sale_drop_pity.get(&(sale_id, account_id)).unwrap_or_default();
+    pity.resize(drop_table.pity.len(), 0);
+
+    pity
+  }
+}
+
+impl SaleReveal for NonFungibleToken {
+  fn nft_sale_reveal(&mut self, reservation_id: SaleReservationId) -> Vec<TokenId> {
+    self.internal_sale_reveal(&reservation_id)
+  }
+
+  fn nft_sale_refund(&mut self, reservation_id: SaleReservationId) -> U128 {
+    U128::from(self.internal_sale_refund(&reservation_id))
+  }
+
+  fn nft_sale_reservation(&self, reservation_id: SaleReservationId) -> Option<SaleReservation> {
+    self.sale_reservation_by_id.get(&reservation_id)
+  }
+
+  fn nft_sale_reservations(&self, account_id: AccountId) -> Vec<SaleReservation> {
+    self.sale_reservations_per_account
+      .get(&account_id)
+      .map(|reservations| {
+        reservations
+          .iter()
+          .map(|reservation_id| self.sale_reservation_by_id.get(&reservation_id).unwrap())
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+}
+
+impl SalePhases for NonFungibleToken {
+  fn nft_sale_set_phases(&mut self, sale_id: SaleId, phases: Vec<SalePhase>) -> Vec<SalePhase> {
+    self.assert_owner();
+
+    self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+
+    phases.iter().enumerate().for_each(|(index, phase)| {
+      assert!(phase.start_date < phase.end_date, "Phase {} must end after start", phase.name);
+      assert!(phase.buy_max > 0, "Phase {} buy max must be greater than 0", phase.name);
+      assert!(phases[..index].iter().all(|p| p.name != phase.name), "Phase {} is duplicated", phase.name);
+
+      if let Some(prev) = index.checked_sub(1).map(|i| &phases[i]) {
+        assert!(prev.end_date <= phase.start_date, "Phase {} overlaps {}", phase.name, prev.name);
+      }
+    });
+
+    self.sale_phases_by_id.insert(&sale_id, &phases);
+
+    SalePhasesUpdate {
+      sale_id: &sale_id,
+      phases: &phases,
+    }.emit();
+
+    phases
+  }
+
+  fn nft_sale_phases(&self, sale_id: SaleId) -> Vec<SalePhase> {
+    self.sale_phases_by_id.get(&sale_id).unwrap_or_default()
+  }
+
+  fn nft_sale_current_phase(&self, sale_id: SaleId) -> Option<SalePhase> {
+    self.internal_sale_current_phase(&sale_id)
+  }
+
+  fn nft_sale_phase_minted(&self, sale_id: SaleId, phase_name: String, account_id: AccountId) -> u32 {
+    self.sale_phase_mint_counter.get(&(sale_id, phase_name, account_id)).unwrap_or(0)
+  }
+}
+
+impl SaleTreasury for NonFungibleToken {
+  fn nft_sale_set_beneficiaries(&mut self, sale_id: SaleId, beneficiaries: HashMap<AccountId, u32>) {
+    self.assert_owner();
+
+    self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+
+    assert!(!beneficiaries.is_empty(), "Beneficiaries are required");
+    assert!(beneficiaries.len() <= 10, "Cannot add more than 10 beneficiaries");
+    assert_eq!(beneficiaries.values().sum::<u32>(), 10_000, "Beneficiary shares must sum to 10000");
+
+    self.sale_beneficiaries_by_id.insert(&sale_id, &beneficiaries);
+  }
+
+  fn nft_sale_withdraw(&mut self, sale_id: SaleId) -> Vec<SaleProceeds> {
+    self.assert_owner();
+
+    let mut proceeds = self.sale_proceeds_by_id.get(&sale_id).expect("Not found sale proceeds");
+    let sale_ft_token = self.sale_by_ft_token.as_ref().unwrap().get(&sale_id);
+    let reserved = self.internal_dutch_auction_reserved(&sale_id);
+
+    for p in proceeds.iter_mut() {
+      let mut pending = p.total.0 - p.withdrawn.0;
+
+      // keep what buyers can still claim back as rebates
+      if p.ft_token == sale_ft_token {
+        pending = pending.saturating_sub(reserved);
+      }
+
+      if pending == 0 {
+        continue;
+      }
+
+      p.withdrawn = U128::from(p.withdrawn.0 + pending);
+
+      self.internal_sale_split(&sale_id, pending)
+        .iter()
+        .filter(|(_, amount)| *amount > 0)
+        .for_each(|(receiver_id, amount)| {
+          self.internal_sale_payout(&sale_id, &p.ft_token, receiver_id, *amount);
+        });
+    }
+
+    self.sale_proceeds_by_id.insert(&sale_id, &proceeds);
+
+    proceeds
+  }
+
+  fn nft_sale_revenue(&self, sale_id: SaleId) -> Vec<SaleProceeds> {
+    self.sale_proceeds_by_id.get(&sale_id).unwrap_or_default()
+  }
+
+  fn nft_sale_beneficiaries(&self, sale_id: SaleId) -> HashMap<AccountId, u32> {
+    self.sale_beneficiaries_by_id.get(&sale_id).unwrap_or_else(|| {
+      let mut beneficiaries = HashMap::new();
+      beneficiaries.insert(self.owner_id.clone(), 10_000);
+      beneficiaries
+    })
+  }
+}
+
+impl SaleDutchAuction for NonFungibleToken {
+  fn nft_sale_set_dutch_auction(&mut self, sale_id: SaleId, auction: Option<DutchAuction>) -> Option<DutchAuction> {
+    self.assert_owner();
+
+    self.sale_by_id.as_ref().unwrap().get(&sale_id).expect("Not found sale");
+    assert!(self.sales_locked.as_ref().unwrap().contains(&sale_id), "Sale already unlocked");
+
+    match &auction {
+      Some(auction) => {
+        assert!(auction.floor_price.0 <= auction.start_price.0, "Floor price must not exceed start price");
+        assert!(auction.interval > 0, "Interval must be greater than 0");
+
+        self.sale_dutch_by_id.insert(&sale_id, auction);
+      }
+      None => {
+        self.sale_dutch_by_id.remove(&sale_id);
+      }
+    }
+
+    auction
+  }
+
+  fn nft_sale_dutch_auction(&self, sale_id: SaleId) -> Option<DutchAuction> {
+    self.sale_dutch_by_id.get(&sale_id)
+  }
+
+  fn nft_sale_current_price(&self, sale_id: SaleId) -> U128 {
+    U128::from(self.internal_sale_price(&sale_id))
+  }
+
+  fn nft_sale_rebate(&self, sale_id: SaleId, account_id: AccountId) -> U128 {
+    if !self.internal_dutch_auction_cleared(&sale_id) {
+      return U128::from(0);
+    }
+
+    let totals = self.sale_dutch_totals.get(&sale_id).unwrap_or_default();
+    let (count, paid) = self.sale_dutch_purchases.get(&(sale_id, account_id)).unwrap_or((0, 0));
+
+    U128::from(paid - totals.clearing_price * (count as u128))
+  }
+
+  fn nft_sale_claim_rebate(&mut self, sale_id: SaleId) -> U128 {
+    let account_id = env::predecessor_account_id();
+
+    assert!(self.internal_dutch_auction_cleared(&sale_id), "Dutch auction is not cleared");
+
+    let (count, paid) = self.sale_dutch_purchases.remove(&(sale_id.clone(), account_id.clone())).expect("Nothing to claim");
+
+    let mut totals = self.sale_dutch_totals.get(&sale_id).expect("Nothing to claim");
+    let rebate = paid - totals.clearing_price * (count as u128);
+
+    totals.sold -= count;
+    totals.paid -= paid;
+    self.sale_dutch_totals.insert(&sale_id, &totals);
+
+    if rebate > 0 {
+      self.internal_sale_revenue_change(&sale_id, rebate, false);
+      self.internal_sale_rebate_payout(&sale_id, &account_id, count, paid, rebate);
+    }
+
+    U128::from(rebate)
+  }
+}
+
+impl SaleResolver for NonFungibleToken {
+  fn nft_resolve_sale_withdraw(
+    &mut self,
+    sale_id: SaleId,
+    ft_token: Option<AccountId>,
+    receiver_id: AccountId,
+    amount: U128,
+  ) -> U128 {
+    let success = match env::promise_result(0) {
+      PromiseResult::NotReady => env::abort(),
+      PromiseResult::Successful(_) => true,
+      PromiseResult::Failed => false,
+    };
+
+    if success {
+      SaleWithdraw {
+        sale_id: &sale_id,
+        ft_token: ft_token.as_ref(),
+        receiver_id: &receiver_id,
+        amount: &amount,
+      }.emit();
+
+      return amount;
+    }
+
+    // transfer failed, the amount is back on the contract and can be withdrawn again
+    let mut proceeds = self.sale_proceeds_by_id.get(&sale_id).expect("Not found sale proceeds");
+    if let Some(p) = proceeds.iter_mut().find(|p| p.ft_token == ft_token) {
+      p.withdrawn = U128::from(p.withdrawn.0 - amount.0);
+    }
+    self.sale_proceeds_by_id.insert(&sale_id, &proceeds);
+
+    U128::from(0)
+  }
+
+  fn nft_resolve_sale_rebate(
+    &mut self,
+    sale_id: SaleId,
+    account_id: AccountId,
+    count: u64,
+    paid: U128,
+    amount: U128,
+  ) -> U128 {
+    let success = match env::promise_result(0) {
+      PromiseResult::NotReady => env::abort(),
+      PromiseResult::Successful(_) => true,
+      PromiseResult::Failed => false,
+    };
+
+    if success {
+      return amount;
+    }
+
+    // transfer failed, the rebate can be claimed again
+    let key = (sale_id.clone(), account_id);
+    let (prev_count, prev_paid) = self.sale_dutch_purchases.get(&key).unwrap_or((0, 0));
+    self.sale_dutch_purchases.insert(&key, &(prev_count + count, prev_paid + paid.0));
+
+    let mut totals = self.sale_dutch_totals.get(&sale_id).unwrap_or_default();
+    totals.sold += count;
+    totals.paid += paid.0;
+    self.sale_dutch_totals.insert(&sale_id, &totals);
+
+    self.internal_sale_revenue_change(&sale_id, amount.0, true);
+
+    U128::from(0)
   }
 }
 
@@ -152,9 +657,7 @@ impl SaleEnumeration for NonFungibleToken {
   }
 
   fn nft_sale_not_minted(&self, sale_id: SaleId) -> u64 {
-    let rand_tokens = self.sale_random_tokens.as_ref().unwrap().get(&sale_id).expect("Not found sale");
-
-    rand_tokens.len() as u64
+    self.internal_sale_not_minted(&sale_id).expect("Not found sale")
   }
 
   fn nft_sales_locked(&self) -> Vec<String> {
@@ -202,3 +705,232 @@ impl SaleEnumeration for NonFungibleToken {
     owner_minted
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::nft::{storage_cost, TokenRarity};
+  use crate::nft::sale::{DropEntry, DropKey, DropPity};
+  use crate::nft::test_utils::*;
+
+  const START: u64 = 1_000;
+
+  fn sale(tokens: &mut NonFungibleToken, amount: u64, dutch_auction: Option<DutchAuction>) -> SaleId {
+    let sale_id: SaleId = "sale".to_string();
+
+    tokens.nft_sale_add(sale_id.clone(), "Sale".to_string(), amount, U128::from(NEAR), 1, 5, 5, None);
+    tokens.nft_sale_set_dutch_auction(sale_id.clone(), dutch_auction);
+    (0..amount).for_each(|index| {
+      mint(tokens, &format!("token{}", index), Some(&owner()), Some(sale_id.clone()));
+    });
+    tokens.nft_sale_start(sale_id.clone(), START, None);
+
+    sale_id
+  }
+
+  fn drop_sale(tokens: &mut NonFungibleToken, drop_table: DropTable, rarities: &[TokenRarity]) -> SaleId {
+    let sale_id: SaleId = "sale".to_string();
+
+    tokens.nft_sale_add(sale_id.clone(), "Sale".to_string(), rarities.len() as u64, U128::from(NEAR), 1, 5, 5, None);
+    tokens.nft_sale_set_drop_table(sale_id.clone(), Some(drop_table));
+    rarities.iter().enumerate().for_each(|(index, rarity)| {
+      mint_with_rarity(tokens, &format!("token{}", index), Some(&owner()), Some(sale_id.clone()), rarity.clone());
+    });
+    tokens.nft_sale_start(sale_id.clone(), START, None);
+
+    sale_id
+  }
+
+  fn drop_entry(rarity: TokenRarity, weight: u32) -> DropEntry {
+    DropEntry {
+      key: DropKey::Rarity { rarity },
+      weight,
+    }
+  }
+
+  fn dutch_auction() -> DutchAuction {
+    DutchAuction {
+      start_price: U128::from(10 * NEAR),
+      floor_price: U128::from(4 * NEAR),
+      interval: 100,
+      step: U128::from(2 * NEAR),
+      rebate: true,
+    }
+  }
+
+  fn buy(tokens: &mut NonFungibleToken, sale_id: &SaleId, buyer_id: &AccountId, timestamp: u64) -> SaleReservation {
+    buy_amount(tokens, sale_id, buyer_id, timestamp, 1)
+  }
+
+  fn buy_amount(tokens: &mut NonFungibleToken, sale_id: &SaleId, buyer_id: &AccountId, timestamp: u64, amount: u64) -> SaleReservation {
+    set_context(context(buyer_id, 20 * NEAR).block_timestamp(timestamp).block_index(1));
+
+    tokens.nft_buy(buyer_id.clone(), sale_id.clone(), amount, None)
+  }
+
+  fn reveal(tokens: &mut NonFungibleToken, reservation: &SaleReservation, block_index: u64) -> Vec<TokenId> {
+    set_context(context(&contract(), 0).block_timestamp(START + 1_000).block_index(block_index));
+
+    tokens.nft_sale_reveal(reservation.id)
+  }
+
+  #[test]
+  fn dutch_price_drops_by_step_to_floor() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 1, Some(dutch_auction()));
+
+    let price_at = |tokens: &NonFungibleToken, timestamp: u64| {
+      set_context(context(&owner(), 0).block_timestamp(timestamp));
+      tokens.nft_sale_current_price(sale_id.clone()).0
+    };
+
+    assert_eq!(price_at(&tokens, START - 1), 10 * NEAR);
+    assert_eq!(price_at(&tokens, START + 99), 10 * NEAR);
+    assert_eq!(price_at(&tokens, START + 150), 8 * NEAR);
+    assert_eq!(price_at(&tokens, START + 300), 4 * NEAR);
+    assert_eq!(price_at(&tokens, START + 10_000), 4 * NEAR);
+  }
+
+  #[test]
+  fn dutch_rebate_is_paid_above_lowest_revealed_price() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 3, Some(dutch_auction()));
+    let (alice, bob, carol) = (account("alice"), account("bob"), account("carol"));
+
+    let alice_reservation = buy(&mut tokens, &sale_id, &alice, START);
+    let bob_reservation = buy(&mut tokens, &sale_id, &bob, START + 150);
+    let carol_reservation = buy(&mut tokens, &sale_id, &carol, START + 300);
+
+    // the cheapest purchase is revealed first, later ones must not raise the clearing price
+    reveal(&mut tokens, &carol_reservation, 2);
+    reveal(&mut tokens, &alice_reservation, 2);
+    assert_eq!(tokens.nft_sale_rebate(sale_id.clone(), alice.clone()).0, 0, "Rebate before the auction is cleared");
+    assert_eq!(tokens.internal_dutch_auction_reserved(&sale_id), 14 * NEAR - 2 * 4 * NEAR);
+    reveal(&mut tokens, &bob_reservation, 2);
+
+    assert_eq!(tokens.sale_dutch_totals.get(&sale_id).unwrap().clearing_price, 4 * NEAR);
+    assert_eq!(tokens.nft_sale_rebate(sale_id.clone(), alice.clone()).0, 6 * NEAR);
+    assert_eq!(tokens.nft_sale_rebate(sale_id.clone(), bob.clone()).0, 4 * NEAR);
+    assert_eq!(tokens.nft_sale_rebate(sale_id.clone(), carol.clone()).0, 0);
+    assert_eq!(tokens.internal_dutch_auction_reserved(&sale_id), 10 * NEAR);
+
+    set_context(context(&alice, 0).block_index(3));
+    assert_eq!(tokens.nft_sale_claim_rebate(sale_id.clone()).0, 6 * NEAR);
+    assert_eq!(transfers(), vec![(alice.clone(), 6 * NEAR)]);
+    assert_eq!(tokens.nft_sale_rebate(sale_id.clone(), alice).0, 0);
+    assert_eq!(tokens.internal_dutch_auction_reserved(&sale_id), 4 * NEAR);
+  }
+
+  #[test]
+  #[should_panic(expected = "Reservation can be revealed only in a later block")]
+  fn reveal_in_purchase_block_fails() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 1, None);
+    let reservation = buy(&mut tokens, &sale_id, &account("alice"), START);
+
+    reveal(&mut tokens, &reservation, 1);
+  }
+
+  #[test]
+  fn reveal_transfers_tokens_and_refunds_unused_storage() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 2, None);
+    let alice = account("alice");
+    let reservation = buy(&mut tokens, &sale_id, &alice, START);
+    assert!(reservation.storage > 0);
+
+    let token_ids = reveal(&mut tokens, &reservation, 2);
+
+    assert_eq!(token_ids.len(), 1);
+    assert_eq!(tokens.owner_by_id.get(&token_ids[0]), Some(alice.clone()));
+    assert!(tokens.nft_sale_reservation(reservation.id).is_none());
+    assert!(transfers().iter().all(|(account_id, amount)| account_id == &alice && *amount < storage_cost(reservation.storage)));
+  }
+
+  #[test]
+  #[should_panic(expected = "Reservation is expired")]
+  fn reveal_after_timeout_fails() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 1, None);
+    let reservation = buy(&mut tokens, &sale_id, &account("alice"), START);
+
+    reveal(&mut tokens, &reservation, 1_002);
+  }
+
+  #[test]
+  #[should_panic(expected = "Reservation is not expired")]
+  fn refund_before_timeout_fails() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 1, None);
+    let reservation = buy(&mut tokens, &sale_id, &account("alice"), START);
+
+    set_context(context(&account("anyone"), 0).block_index(1_001));
+    tokens.nft_sale_refund(reservation.id);
+  }
+
+  #[test]
+  fn refund_after_timeout_returns_payment_and_slots() {
+    let mut tokens = new_tokens();
+    let sale_id = sale(&mut tokens, 1, None);
+    let alice = account("alice");
+    let reservation = buy(&mut tokens, &sale_id, &alice, START);
+    assert_eq!(tokens.internal_sale_available(&sale_id), 0);
+
+    set_context(context(&account("anyone"), 0).block_index(1_002));
+    assert_eq!(tokens.nft_sale_refund(reservation.id).0, NEAR);
+
+    let refunds = transfers();
+    assert!(refunds.contains(&(alice.clone(), NEAR)));
+    assert!(refunds.contains(&(alice.clone(), storage_cost(reservation.storage))));
+    // a failed refund stays claimable
+    assert_eq!(function_calls(), vec!["nft_resolve_payout".to_string()]);
+    assert_eq!(tokens.internal_sale_available(&sale_id), 1);
+    assert_eq!(tokens.nft_sale_account_minted(sale_id, alice.clone()), 0);
+    assert!(tokens.nft_sale_reservations(alice).is_empty());
+  }
+
+  #[test]
+  fn drop_odds_follow_weights_of_not_empty_entries() {
+    let mut tokens = new_tokens();
+    let drop_table = DropTable {
+      entries: vec![drop_entry(TokenRarity::Common, 900), drop_entry(TokenRarity::Rare, 100), drop_entry(TokenRarity::Epic, 50)],
+      pity: vec![],
+    };
+    let sale_id = drop_sale(&mut tokens, drop_table, &[TokenRarity::Common, TokenRarity::Common, TokenRarity::Common, TokenRarity::Rare]);
+
+    let odds: Vec<(u32, u64)> = tokens.nft_sale_drop_odds(sale_id).into_iter().map(|odds| (odds.odds, odds.remaining)).collect();
+
+    assert_eq!(odds, vec![(9_000, 3), (1_000, 1), (0, 0)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "Token does not match the drop table")]
+  fn token_outside_drop_table_cannot_be_added() {
+    let mut tokens = new_tokens();
+    let drop_table = DropTable {
+      entries: vec![drop_entry(TokenRarity::Common, 1)],
+      pity: vec![],
+    };
+
+    drop_sale(&mut tokens, drop_table, &[TokenRarity::Rare]);
+  }
+
+  #[test]
+  fn pity_gives_the_entry_within_every_draws() {
+    let mut tokens = new_tokens();
+    let drop_table = DropTable {
+      entries: vec![drop_entry(TokenRarity::Common, 1_000_000), drop_entry(TokenRarity::Rare, 1)],
+      pity: vec![DropPity { entry: 1, every: 3 }],
+    };
+    let sale_id = drop_sale(&mut tokens, drop_table, &[TokenRarity::Common, TokenRarity::Common, TokenRarity::Common, TokenRarity::Rare]);
+    let alice = account("alice");
+
+    let reservation = buy_amount(&mut tokens, &sale_id, &alice, START, 3);
+    let token_ids = reveal(&mut tokens, &reservation, 2);
+
+    let rarities: Vec<TokenRarity> = token_ids.iter().map(|token_id| tokens.token_rarity_by_id.as_ref().unwrap().get(token_id).unwrap()).collect();
+    assert!(rarities.contains(&TokenRarity::Rare));
+    assert_eq!(tokens.nft_sale_drop_pity(sale_id.clone(), alice), vec![0]);
+    assert_eq!(tokens.nft_sale_drop_odds(sale_id)[1].remaining, 0);
+  }
+}
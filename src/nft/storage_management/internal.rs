@@ -0,0 +1,94 @@
+use crate::nft::{NonFungibleToken, StorageBalance, refund_deposit_to_account_with_price, storage_cost};
+use near_sdk::{env, AccountId, Balance, Promise, StorageUsage};
+use near_sdk::json_types::U128;
+
+// max account id length, borsh length, balance, trie overhead and storage key prefix
+const BYTES_PER_ACCOUNT: StorageUsage = 64 + 4 + 16 + 40 + 1;
+
+impl NonFungibleToken {
+  pub(crate) fn internal_storage_balance_min(&self) -> Balance {
+    storage_cost(BYTES_PER_ACCOUNT)
+  }
+
+  pub(crate) fn internal_storage_balance_of(&self, account_id: &AccountId) -> Option<StorageBalance> {
+    self.storage_balance_by_id.get(account_id).map(|total| StorageBalance {
+      total: U128::from(total),
+      available: U128::from(total.saturating_sub(self.internal_storage_balance_min())),
+    })
+  }
+
+  /// Pay for `storage_used` from the storage balance of `account_id`.
+  /// Returns `false` if the account is not registered.
+  pub(crate) fn internal_storage_use(&mut self, account_id: &AccountId, storage_used: StorageUsage) -> bool {
+    let total = match self.storage_balance_by_id.get(account_id) {
+      Some(total) => total,
+      None => return false,
+    };
+
+    if storage_used == 0 {
+      return true;
+    }
+
+    let cost = storage_cost(storage_used);
+    let available = total.saturating_sub(self.internal_storage_balance_min());
+
+    if cost > available {
+      env::panic_str(&format!("Not enough storage balance, {} yoctoNEAR required", cost));
+    }
+
+    self.storage_balance_by_id.insert(account_id, &(total - cost));
+
+    true
+  }
+
+  /// Pay for `storage_used` from the storage balance of `account_id`, used when no NEAR is attached
+  /// like in FT transfer calls, so the account must be registered
+  pub(crate) fn internal_storage_use_registered(&mut self, account_id: &AccountId, storage_used: StorageUsage) {
+    if !self.internal_storage_use(account_id, storage_used) {
+      env::panic_str("Account is not registered, call storage_deposit first");
+    }
+  }
+
+  /// Pay for `storage_used` from the storage balance of `account_id`, or from `deposit` if the
+  /// account is not registered. Returns the rest of `deposit`.
+  pub(crate) fn internal_storage_use_deposit(&mut self, account_id: &AccountId, storage_used: StorageUsage, deposit: Balance) -> Balance {
+    if self.internal_storage_use(account_id, storage_used) {
+      return deposit;
+    }
+
+    let cost = storage_cost(storage_used);
+    assert!(deposit >= cost, "Must attach {} yoctoNEAR to cover storage", cost);
+
+    deposit - cost
+  }
+
+  /// Pay `price` from the attached deposit and `storage_used` from the storage balance of
+  /// `account_id`, or from the attached deposit if the account is not registered.
+  /// The rest of the attached deposit is refunded to `account_id`.
+  pub(crate) fn internal_storage_charge(&mut self, account_id: &AccountId, storage_used: StorageUsage, price: Balance) {
+    if self.internal_storage_use(account_id, storage_used) {
+      refund_deposit_to_account_with_price(0, price, account_id.clone());
+    } else {
+      refund_deposit_to_account_with_price(storage_used, price, account_id.clone());
+    }
+  }
+
+  /// Settle storage paid in advance by `account_id`, `used` of the `paid` bytes was taken. The rest
+  /// goes back to the storage balance of a registered account or is sent as NEAR.
+  pub(crate) fn internal_storage_settle(&mut self, account_id: &AccountId, paid: StorageUsage, used: StorageUsage) {
+    if used >= paid {
+      return;
+    }
+
+    let refund = storage_cost(paid - used);
+
+    match self.storage_balance_by_id.get(account_id) {
+      Some(total) => {
+        self.storage_balance_by_id.insert(account_id, &(total + refund));
+      }
+      None => {
+        Promise::new(account_id.clone()).transfer(refund);
+      }
+    }
+  }
+}
@@ -0,0 +1,38 @@
+// Storage management
+
+#[macro_export]
+macro_rules! impl_storage_management {
+    ($contract: ident, $tokens: ident) => {
+        use $crate::{StorageManagement, StorageBalance, StorageBalanceBounds};
+
+        #[near_bindgen]
+        impl StorageManagement for $contract {
+          #[payable]
+          fn storage_deposit(
+            &mut self,
+            account_id: Option<AccountId>,
+            registration_only: Option<bool>,
+          ) -> StorageBalance {
+            self.$tokens.storage_deposit(account_id, registration_only)
+          }
+
+          #[payable]
+          fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+            self.$tokens.storage_withdraw(amount)
+          }
+
+          #[payable]
+          fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+            self.$tokens.storage_unregister(force)
+          }
+
+          fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+            self.$tokens.storage_balance_bounds()
+          }
+
+          fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+            self.$tokens.storage_balance_of(account_id)
+          }
+        }
+    };
+}
@@ -0,0 +1,41 @@
+pub use metadata::*;
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+
+pub mod metadata;
+pub mod storage_management_impl;
+mod internal;
+mod macros;
+
+/// [Storage management standard](https://nomicon.io/Standards/StorageManagement.html).
+/// Registered accounts pay for storage of transfers, approvals and purchases from
+/// their storage balance instead of the attached deposit.
+pub trait StorageManagement {
+  /// Payable method that receives an attached deposit of Ⓝ for a given account.
+  ///
+  /// If `account_id` is omitted, the deposit MUST go toward predecessor account.
+  /// If `registration_only=true`, the contract MUST refund above the minimum balance
+  /// if the account wasn't registered and refund full deposit if already registered.
+  fn storage_deposit(
+    &mut self,
+    account_id: Option<AccountId>,
+    registration_only: Option<bool>,
+  ) -> StorageBalance;
+
+  /// Withdraw specified amount of available Ⓝ for predecessor account.
+  ///
+  /// Requires attached deposit of exactly 1 yoctoⓃ. If `amount` is omitted,
+  /// the contract will refund the full `available` balance.
+  fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance;
+
+  /// Unregisters the predecessor account and returns the storage NEAR deposit.
+  ///
+  /// Requires attached deposit of exactly 1 yoctoⓃ. Returns `false` if the account
+  /// was not registered. Panics if the account owns tokens, unless `force=true`,
+  /// in that case the tokens are burned.
+  fn storage_unregister(&mut self, force: Option<bool>) -> bool;
+
+  fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+
+  fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance>;
+}
@@ -0,0 +1,159 @@
+use crate::nft::{NonFungibleToken, StorageBalance, StorageBalanceBounds, StorageManagement};
+use near_sdk::{assert_one_yocto, env, AccountId, Promise};
+use near_sdk::json_types::U128;
+
+impl StorageManagement for NonFungibleToken {
+  fn storage_deposit(
+    &mut self,
+    account_id: Option<AccountId>,
+    registration_only: Option<bool>,
+  ) -> StorageBalance {
+    let amount = env::attached_deposit();
+    let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+    let registration_only = registration_only.unwrap_or(false);
+    let min_balance = self.internal_storage_balance_min();
+
+    if let Some(total) = self.storage_balance_by_id.get(&account_id) {
+      if registration_only {
+        if amount > 0 {
+          Promise::new(env::predecessor_account_id()).transfer(amount);
+        }
+      } else {
+        self.storage_balance_by_id.insert(&account_id, &(total + amount));
+      }
+    } else {
+      assert!(amount >= min_balance, "The attached deposit is less than the minimum storage balance");
+
+      let total = if registration_only {
+        let refund = amount - min_balance;
+        if refund > 0 {
+          Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+        min_balance
+      } else {
+        amount
+      };
+
+      self.storage_balance_by_id.insert(&account_id, &total);
+    }
+
+    self.internal_storage_balance_of(&account_id).unwrap()
+  }
+
+  fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+    assert_one_yocto();
+    let account_id = env::predecessor_account_id();
+    let balance = self.internal_storage_balance_of(&account_id)
+      .unwrap_or_else(|| env::panic_str(&format!("The account {} is not registered", &account_id)));
+
+    let amount = amount.map(|amount| amount.0).unwrap_or(balance.available.0);
+
+    assert!(amount <= balance.available.0, "The amount is greater than the available storage balance");
+
+    if amount > 0 {
+      self.storage_balance_by_id.insert(&account_id, &(balance.total.0 - amount));
+      Promise::new(account_id.clone()).transfer(amount);
+    }
+
+    self.internal_storage_balance_of(&account_id).unwrap()
+  }
+
+  fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+    assert_one_yocto();
+    let account_id = env::predecessor_account_id();
+    let force = force.unwrap_or(false);
+
+    let total = match self.storage_balance_by_id.get(&account_id) {
+      Some(total) => total,
+      None => {
+        env::log_str(&format!("The account {} is not registered", &account_id));
+        return false;
+      }
+    };
+
+    let token_ids = self.tokens_per_owner.as_ref().unwrap()
+      .get(&account_id)
+      .map(|tokens| tokens.to_vec())
+      .unwrap_or_default();
+
+    if !token_ids.is_empty() {
+      assert!(force, "Can't unregister the account with the positive number of tokens without force");
+
+      // locked tokens, like fractionation parts or tokens in auctions, can't be burned
+      token_ids.iter().for_each(|token_id| {
+        self.assert_available_burn(token_id, &account_id);
+      });
+      token_ids.iter().for_each(|token_id| {
+        self.internal_burn_token_unguarded(&account_id, token_id);
+      });
+    }
+
+    self.storage_balance_by_id.remove(&account_id);
+    Promise::new(account_id).transfer(total + 1);
+
+    true
+  }
+
+  fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+    StorageBalanceBounds {
+      min: U128::from(self.internal_storage_balance_min()),
+      max: None,
+    }
+  }
+
+  fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+    self.internal_storage_balance_of(&account_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::nft::NonFungibleTokenCore;
+  use crate::nft::test_utils::*;
+
+  fn register(tokens: &mut NonFungibleToken, account_id: &AccountId, amount: u128) {
+    set_context(&context(account_id, amount));
+    tokens.storage_deposit(None, None);
+  }
+
+  #[test]
+  fn force_unregister_burns_tokens() {
+    let mut tokens = new_tokens();
+    let alice = account("alice");
+    register(&mut tokens, &alice, NEAR);
+    mint(&mut tokens, "token", Some(&alice), None);
+
+    set_context(&context(&alice, 1));
+    assert!(tokens.storage_unregister(Some(true)));
+
+    assert!(tokens.owner_by_id.get(&"token".to_string()).is_none());
+    assert!(tokens.storage_balance_of(alice).is_none());
+  }
+
+  #[test]
+  fn transfer_uses_storage_balance() {
+    let mut tokens = new_tokens();
+    let alice = account("alice");
+    register(&mut tokens, &alice, NEAR);
+    mint(&mut tokens, "a", Some(&alice), None);
+    mint(&mut tokens, "b", Some(&alice), None);
+
+    set_context(&context(&alice, 1));
+    tokens.nft_transfer(account("bob"), "a".to_string(), None, None);
+
+    assert!(tokens.storage_balance_of(alice).unwrap().total.0 < NEAR);
+  }
+
+  #[test]
+  #[should_panic(expected = "to cover storage")]
+  fn transfer_of_unregistered_sender_needs_storage_deposit() {
+    let mut tokens = new_tokens();
+    let alice = account("alice");
+    mint(&mut tokens, "a", Some(&alice), None);
+    mint(&mut tokens, "b", Some(&alice), None);
+
+    set_context(&context(&alice, 1));
+    tokens.nft_transfer(account("bob"), "a".to_string(), None, None);
+  }
+}
@@ -0,0 +1,114 @@
+use near_sdk::{testing_env, AccountId, Balance, PromiseResult, RuntimeFeesConfig, VMConfig};
+use near_sdk::mock::VmAction;
+use near_sdk::test_utils::{get_created_receipts, VMContextBuilder};
+use crate::nft::{NonFungibleToken, TokenId, Token, TokenCollection, TokenRarity, TokenType, SaleId};
+use crate::nft::metadata::TokenMetadata;
+
+pub(crate) const NEAR: Balance = 1_000_000_000_000_000_000_000_000;
+
+pub(crate) fn account(name: &str) -> AccountId {
+  format!("{}.near", name).parse().unwrap()
+}
+
+pub(crate) fn owner() -> AccountId {
+  account("owner")
+}
+
+pub(crate) fn contract() -> AccountId {
+  account("nft")
+}
+
+/// Context of a call by `predecessor_id` with `deposit` attached, storage of the previous calls is kept
+pub(crate) fn context(predecessor_id: &AccountId, deposit: Balance) -> VMContextBuilder {
+  let mut builder = VMContextBuilder::new();
+  builder
+    .current_account_id(contract())
+    .signer_account_id(predecessor_id.clone())
+    .predecessor_account_id(predecessor_id.clone())
+    .account_balance(1_000 * NEAR)
+    .attached_deposit(deposit);
+
+  builder
+}
+
+pub(crate) fn set_context(builder: &VMContextBuilder) {
+  testing_env!(builder.build());
+}
+
+/// Context of a callback of the contract to itself with the result of the previous promise
+pub(crate) fn set_callback_context(result: PromiseResult) {
+  testing_env!(context(&contract(), 0).build(), VMConfig::test(), RuntimeFeesConfig::test(), Default::default(), vec![result]);
+}
+
+pub(crate) fn new_tokens() -> NonFungibleToken {
+  set_context(&context(&owner(), 0));
+
+  crate::Contract::new_with_default_meta(owner()).tokens
+}
+
+pub(crate) fn metadata(title: &str) -> TokenMetadata {
+  TokenMetadata {
+    title: Some(title.to_string()),
+    description: None,
+    media: None,
+    media_hash: None,
+    copies: None,
+    issued_at: None,
+    expires_at: None,
+    starts_at: None,
+    updated_at: None,
+    extra: None,
+    reference: None,
+    reference_hash: None,
+  }
+}
+
+pub(crate) fn mint(tokens: &mut NonFungibleToken, token_id: &str, owner_id: Option<&AccountId>, sale_id: Option<SaleId>) -> Token {
+  mint_with_rarity(tokens, token_id, owner_id, sale_id, TokenRarity::Common)
+}
+
+pub(crate) fn mint_with_rarity(tokens: &mut NonFungibleToken, token_id: &str, owner_id: Option<&AccountId>, sale_id: Option<SaleId>, rarity: TokenRarity) -> Token {
+  let token_id: TokenId = token_id.to_string();
+
+  tokens.internal_create_nft_with_refund(
+    &token_id,
+    owner_id.cloned(),
+    Some(metadata(&token_id)),
+    rarity,
+    TokenCollection::Nordic,
+    None,
+    sale_id,
+    None,
+    None,
+    TokenType::Weapon,
+    None,
+    None,
+  )
+}
+
+/// NEAR transfers of the last call
+pub(crate) fn transfers() -> Vec<(AccountId, Balance)> {
+  get_created_receipts()
+    .into_iter()
+    .flat_map(|receipt| {
+      let receiver_id = receipt.receiver_id;
+
+      receipt.actions.into_iter().filter_map(move |action| match action {
+        VmAction::Transfer { deposit } => Some((receiver_id.clone(), deposit)),
+        _ => None,
+      })
+    })
+    .collect()
+}
+
+/// Methods called by receipts of the last call
+pub(crate) fn function_calls() -> Vec<String> {
+  get_created_receipts()
+    .into_iter()
+    .flat_map(|receipt| receipt.actions)
+    .filter_map(|action| match action {
+      VmAction::FunctionCall { function_name, .. } => Some(function_name),
+      _ => None,
+    })
+    .collect()
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use near_sdk::{AccountId, env, Balance, Promise};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, transfer_near_or_ft};
+use crate::nft::transfer_fee::{TransferFee, TransferFeeDeposit};
+use crate::nft::events_171_mf::TransferRoyalty;
+
+/// Refund the deposit except 1 yoctoNEAR required by the transfer
+pub(crate) fn refund_transfer_deposit(sender_id: &AccountId, deposit: Balance) {
+  if deposit > 1 {
+    Promise::new(sender_id.clone()).transfer(deposit - 1);
+  }
+}
+
+impl NonFungibleToken {
+  pub(crate) fn internal_transfer_fee(&self, token_id: &TokenId) -> Option<TransferFee> {
+    self.transfer_fee_by_token.get(token_id).or_else(|| {
+      self.token_collection_by_id.as_ref().unwrap()
+        .get(token_id)
+        .and_then(|collection| self.transfer_fee_by_collection.get(&collection))
+    })
+  }
+
+  /// Fee of a transfer from `owner_id` to `receiver_id`, `None` when the transfer is free
+  pub(crate) fn internal_transfer_fee_required(&self, token_id: &TokenId, owner_id: &AccountId, receiver_id: &AccountId) -> Option<TransferFee> {
+    if self.transfer_fee_exempt.contains(owner_id) || self.transfer_fee_exempt.contains(receiver_id) {
+      return None;
+    }
+
+    self.internal_transfer_fee(token_id)
+  }
+
+  /// Check that a sale of `token_id` at `price` is not cheaper than the transfer fee price, sales pay royalties instead of the fee
+  pub(crate) fn assert_transfer_fee_price(&self, token_id: &TokenId, owner_id: &AccountId, receiver_id: &AccountId, price: Balance) {
+    if let Some(fee) = self.internal_transfer_fee_required(token_id, owner_id, receiver_id) {
+      assert!(price >= fee.min_price.0, "Price is lower than the minimal transfer price");
+    }
+  }
+
+  /// Royalties of a transfer from `owner_id` to `receiver_id`, `None` when the transfer is free
+  pub(crate) fn internal_transfer_royalties(&self, token_id: &TokenId, owner_id: &AccountId, receiver_id: &AccountId, price: Option<Balance>) -> Option<(Balance, HashMap<AccountId, U128>)> {
+    let fee = self.internal_transfer_fee_required(token_id, owner_id, receiver_id)?;
+    let price = price.unwrap_or(fee.min_price.0);
+    assert!(price >= fee.min_price.0, "Price is lower than the minimal transfer price");
+
+    let mut payout = self.internal_payout(token_id, owner_id, price, None);
+    payout.remove(owner_id);
+
+    Some((price, payout))
+  }
+
+  /// Check that `deposit` covers royalties of the transfer, `None` when the transfer is free
+  pub(crate) fn assert_transfer_fee_deposit(&self, token_id: &TokenId, owner_id: &AccountId, receiver_id: &AccountId, price: Option<Balance>, deposit: Balance) -> Option<TransferFeeDeposit> {
+    let (price, payout) = self.internal_transfer_royalties(token_id, owner_id, receiver_id, price)?;
+
+    let royalties: Balance = payout.values().map(|amount| amount.0).sum();
+
+    assert!(deposit >= royalties, "Must attach {} yoctoNEAR of transfer royalties", royalties);
+
+    Some(TransferFeeDeposit {
+      sender_id: env::predecessor_account_id(),
+      price: U128::from(price),
+      deposit: U128::from(deposit),
+      payout,
+    })
+  }
+
+  /// Pay royalties of a kept transfer from the deposit and refund the rest to the sender
+  pub(crate) fn internal_transfer_fee_pay(&mut self, token_id: &TokenId, owner_id: &AccountId, receiver_id: &AccountId, fee: &TransferFeeDeposit) {
+    let royalties: Balance = fee.payout.values().map(|amount| amount.0).sum();
+
+    fee.payout
+      .iter()
+      .filter(|(_, amount)| amount.0 > 0)
+      .for_each(|(account_id, amount)| {
+        transfer_near_or_ft(&None, account_id, amount.0, format!("Transfer {} royalty", token_id));
+      });
+
+    let refund = fee.deposit.0 - royalties;
+    if refund > 1 {
+      Promise::new(fee.sender_id.clone()).transfer(refund);
+    }
+
+    TransferRoyalty {
+      token_id,
+      owner_id,
+      receiver_id,
+      price: &fee.price,
+      royalties: &U128::from(royalties),
+    }.emit();
+  }
+
+  /// Pay royalties of the transfer that was just made from `deposit` and refund the rest to `sender_id`
+  pub(crate) fn internal_transfer_fee_charge(&mut self, token_id: &TokenId, sender_id: &AccountId, owner_id: &AccountId, receiver_id: &AccountId, price: Option<Balance>, deposit: Balance) {
+    match self.assert_transfer_fee_deposit(token_id, owner_id, receiver_id, price, deposit) {
+      Some(fee) => self.internal_transfer_fee_pay(token_id, owner_id, receiver_id, &fee),
+      None => refund_transfer_deposit(sender_id, deposit),
+    }
+  }
+}
@@ -0,0 +1,42 @@
+// Transfer fee
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_transfer_fee {
+    ($contract: ident, $tokens: ident $(, $assert_transfer: ident)?) => {
+        use $crate::nft::transfer_fee::{NonFungibleTokenTransferFee, TransferFee};
+
+        #[near_bindgen]
+        impl NonFungibleTokenTransferFee for $contract {
+          fn nft_set_collection_transfer_fee(&mut self, collection: TokenCollection, fee: Option<TransferFee>) {
+            self.$tokens.nft_set_collection_transfer_fee(collection, fee)
+          }
+
+          fn nft_set_token_transfer_fee(&mut self, token_id: TokenId, fee: Option<TransferFee>) {
+            self.$tokens.nft_set_token_transfer_fee(token_id, fee)
+          }
+
+          fn nft_set_transfer_fee_exempt(&mut self, account_id: AccountId, exempt: bool) {
+            self.$tokens.nft_set_transfer_fee_exempt(account_id, exempt)
+          }
+
+          #[payable]
+          fn nft_transfer_with_price(&mut self, receiver_id: AccountId, token_id: TokenId, approval_id: Option<u64>, price: U128, memo: Option<String>) {
+            $(self.$assert_transfer(&token_id, &receiver_id);)?
+
+            self.$tokens.nft_transfer_with_price(receiver_id, token_id, approval_id, price, memo)
+          }
+
+          fn nft_transfer_fee(&self, token_id: TokenId) -> Option<TransferFee> {
+            self.$tokens.nft_transfer_fee(token_id)
+          }
+
+          fn nft_transfer_fee_amount(&self, token_id: TokenId, receiver_id: AccountId, price: Option<U128>) -> U128 {
+            self.$tokens.nft_transfer_fee_amount(token_id, receiver_id, price)
+          }
+
+          fn nft_transfer_fee_exempts(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+            self.$tokens.nft_transfer_fee_exempts(from_index, limit)
+          }
+        }
+    };
+}
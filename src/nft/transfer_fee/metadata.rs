@@ -0,0 +1,22 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use std::collections::HashMap;
+
+/// A plain transfer counts as a sale for `min_price` NEAR, royalties of the price are attached to the transfer
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferFee {
+  pub min_price: U128,
+}
+
+/// Royalties held from the deposit of `nft_transfer_call` until the transfer is resolved
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferFeeDeposit {
+  pub sender_id: AccountId,
+  pub price: U128,
+  pub deposit: U128,
+  pub payout: HashMap<AccountId, U128>,
+}
@@ -0,0 +1,33 @@
+pub use metadata::*;
+use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::{TokenId, TokenCollection};
+
+pub mod metadata;
+pub mod transfer_fee_impl;
+pub(crate) mod internal;
+mod macros;
+
+/// Royalties of `nft_transfer` and `nft_transfer_call` for tokens with a transfer fee, a fee of the token
+/// takes precedence over a fee of its collection. Transfers from or to exempt accounts are free.
+/// `nft_transfer_payout` is not charged as the marketplace pays the royalties, its balance must be
+/// at least the minimal price of the fee. Royalties of `nft_transfer_call` are paid only if the
+/// receiver keeps the token.
+pub trait NonFungibleTokenTransferFee {
+  /// `None` removes the fee
+  fn nft_set_collection_transfer_fee(&mut self, collection: TokenCollection, fee: Option<TransferFee>);
+
+  fn nft_set_token_transfer_fee(&mut self, token_id: TokenId, fee: Option<TransferFee>);
+
+  fn nft_set_transfer_fee_exempt(&mut self, account_id: AccountId, exempt: bool);
+
+  /// Transfer declared as a sale for `price`, royalties of the price are paid from the attached deposit
+  fn nft_transfer_with_price(&mut self, receiver_id: AccountId, token_id: TokenId, approval_id: Option<u64>, price: U128, memo: Option<String>);
+
+  fn nft_transfer_fee(&self, token_id: TokenId) -> Option<TransferFee>;
+
+  /// Deposit required to transfer the token, for `price` or for the minimal price of the fee
+  fn nft_transfer_fee_amount(&self, token_id: TokenId, receiver_id: AccountId, price: Option<U128>) -> U128;
+
+  fn nft_transfer_fee_exempts(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId>;
+}
@@ -0,0 +1,224 @@
+use near_sdk::{env, require, AccountId};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, TokenCollection, assert_at_least_one_yocto, storage_used_since};
+use crate::nft::transfer_fee::{NonFungibleTokenTransferFee, TransferFee};
+use crate::nft::events_171_mf::TransferFeeUpdate;
+
+impl NonFungibleTokenTransferFee for NonFungibleToken {
+  fn nft_set_collection_transfer_fee(&mut self, collection: TokenCollection, fee: Option<TransferFee>) {
+    self.assert_owner();
+
+    match &fee {
+      Some(fee) => self.transfer_fee_by_collection.insert(&collection, fee),
+      None => self.transfer_fee_by_collection.remove(&collection),
+    };
+
+    TransferFeeUpdate {
+      collection: Some(&collection),
+      token_id: None,
+      fee: fee.as_ref(),
+    }.emit();
+  }
+
+  fn nft_set_token_transfer_fee(&mut self, token_id: TokenId, fee: Option<TransferFee>) {
+    self.assert_owner();
+
+    self.owner_by_id.get(&token_id).expect("Not found token");
+
+    match &fee {
+      Some(fee) => self.transfer_fee_by_token.insert(&token_id, fee),
+      None => self.transfer_fee_by_token.remove(&token_id),
+    };
+
+    TransferFeeUpdate {
+      collection: None,
+      token_id: Some(&token_id),
+      fee: fee.as_ref(),
+    }.emit();
+  }
+
+  fn nft_set_transfer_fee_exempt(&mut self, account_id: AccountId, exempt: bool) {
+    self.assert_owner();
+
+    if exempt {
+      self.transfer_fee_exempt.insert(&account_id);
+    } else {
+      self.transfer_fee_exempt.remove(&account_id);
+    }
+  }
+
+  fn nft_transfer_with_price(&mut self, receiver_id: AccountId, token_id: TokenId, approval_id: Option<u64>, price: U128, memo: Option<String>) {
+    assert_at_least_one_yocto();
+
+    let sender_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+
+    let (owner_id, _) = self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+
+    let deposit = self.internal_storage_use_deposit(&sender_id, storage_used_since(initial_storage_usage), env::attached_deposit());
+    self.internal_transfer_fee_charge(&token_id, &sender_id, &owner_id, &receiver_id, Some(price.0), deposit);
+  }
+
+  fn nft_transfer_fee(&self, token_id: TokenId) -> Option<TransferFee> {
+    self.internal_transfer_fee(&token_id)
+  }
+
+  fn nft_transfer_fee_amount(&self, token_id: TokenId, receiver_id: AccountId, price: Option<U128>) -> U128 {
+    let owner_id = self.owner_by_id.get(&token_id).expect("Not found token");
+
+    let royalties = self.internal_transfer_royalties(&token_id, &owner_id, &receiver_id, price.map(|price| price.0))
+      .map(|(_, payout)| payout.values().map(|amount| amount.0).sum())
+      .unwrap_or(0);
+
+    U128::from(royalties)
+  }
+
+  fn nft_transfer_fee_exempts(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.transfer_fee_exempt.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.transfer_fee_exempt
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use near_sdk::PromiseResult;
+  use crate::nft::transfer_fee::TransferFeeDeposit;
+  use crate::nft::NonFungibleTokenCore;
+  use crate::nft::base::NonFungibleTokenResolver;
+  use crate::nft::market::NonFungibleTokenMarket;
+  use crate::nft::payout::NonFungibleTokenPayout;
+  use crate::nft::test_utils::*;
+
+  /// Token of `seller` with 10% royalty to `artist` and a transfer fee of its collection
+  fn token_with_fee(tokens: &mut NonFungibleToken, seller_id: &AccountId) -> TokenId {
+    let token_id = "token".to_string();
+    mint(tokens, &token_id, Some(seller_id), None);
+    tokens.royalty.token_royalty_by_id.insert(&token_id, &vec![(account("artist"), 1_000)].into_iter().collect());
+
+    set_context(&context(&owner(), 0));
+    tokens.nft_set_collection_transfer_fee(TokenCollection::Nordic, Some(TransferFee { min_price: U128::from(10 * NEAR) }));
+
+    token_id
+  }
+
+  fn exempt(tokens: &mut NonFungibleToken, account_id: &AccountId) {
+    set_context(&context(&owner(), 0));
+    tokens.nft_set_transfer_fee_exempt(account_id.clone(), true);
+  }
+
+  #[test]
+  fn transfer_pays_royalties_of_min_price() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = token_with_fee(&mut tokens, &seller);
+    assert_eq!(tokens.nft_transfer_fee_amount(token_id.clone(), account("buyer"), None).0, NEAR);
+
+    set_context(&context(&seller, 2 * NEAR));
+    tokens.nft_transfer(account("buyer"), token_id, None, None);
+
+    assert_eq!(transfers(), vec![(account("artist"), NEAR), (seller, NEAR)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "Must attach 1000000000000000000000000 yoctoNEAR of transfer royalties")]
+  fn transfer_without_royalties_fails() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = token_with_fee(&mut tokens, &seller);
+
+    set_context(&context(&seller, 1));
+    tokens.nft_transfer(account("buyer"), token_id, None, None);
+  }
+
+  #[test]
+  fn transfer_to_exempt_receiver_is_free() {
+    let mut tokens = new_tokens();
+    let (seller, vault) = (account("seller"), account("vault"));
+    let token_id = token_with_fee(&mut tokens, &seller);
+    exempt(&mut tokens, &vault);
+    assert_eq!(tokens.nft_transfer_fee_amount(token_id.clone(), vault.clone(), None).0, 0);
+
+    set_context(&context(&seller, 1));
+    tokens.nft_transfer(vault.clone(), token_id.clone(), None, None);
+
+    assert!(transfers().is_empty());
+    assert_eq!(tokens.owner_by_id.get(&token_id), Some(vault));
+  }
+
+  #[test]
+  fn transfer_from_exempt_sender_refunds_the_deposit() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = token_with_fee(&mut tokens, &seller);
+    exempt(&mut tokens, &seller);
+
+    set_context(&context(&seller, NEAR));
+    tokens.nft_transfer(account("buyer"), token_id, None, None);
+
+    assert_eq!(transfers(), vec![(seller, NEAR - 1)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "Price is lower than the minimal transfer price")]
+  fn market_sale_below_min_price_fails() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = token_with_fee(&mut tokens, &seller);
+
+    set_context(&context(&seller, NEAR));
+    tokens.nft_market_list(token_id.clone(), None, U128::from(1));
+
+    set_context(&context(&account("buyer"), 1));
+    tokens.nft_market_buy(token_id, None);
+  }
+
+  #[test]
+  fn owner_transfers_with_payout() {
+    let mut tokens = new_tokens();
+    let seller = account("seller");
+    let token_id = token_with_fee(&mut tokens, &seller);
+
+    set_context(&context(&seller, 1));
+    let payout = tokens.nft_transfer_payout(account("buyer"), token_id.clone(), None, None, U128::from(10 * NEAR), None);
+
+    assert_eq!(payout.payout.get(&account("artist")), Some(&U128::from(NEAR)));
+    assert_eq!(tokens.owner_by_id.get(&token_id), Some(account("buyer")));
+  }
+
+  #[test]
+  fn transfer_call_pays_royalties_only_when_the_token_is_kept() {
+    let mut tokens = new_tokens();
+    let (seller, receiver) = (account("seller"), account("receiver"));
+    let token_id = token_with_fee(&mut tokens, &seller);
+    let fee = TransferFeeDeposit {
+      sender_id: seller.clone(),
+      price: U128::from(10 * NEAR),
+      deposit: U128::from(2 * NEAR),
+      payout: vec![(account("artist"), U128::from(NEAR))].into_iter().collect(),
+    };
+
+    tokens.internal_transfer(&seller, &receiver, &token_id, None, None);
+    set_callback_context(PromiseResult::Successful(b"true".to_vec()));
+    assert!(!tokens.nft_resolve_transfer(seller.clone(), receiver.clone(), token_id.clone(), None, Some(fee.clone())));
+    assert_eq!(transfers(), vec![(seller.clone(), 2 * NEAR - 1)]);
+    assert_eq!(tokens.owner_by_id.get(&token_id), Some(seller.clone()));
+
+    tokens.internal_transfer(&seller, &receiver, &token_id, None, None);
+    set_callback_context(PromiseResult::Successful(b"false".to_vec()));
+    assert!(tokens.nft_resolve_transfer(seller.clone(), receiver, token_id, None, Some(fee)));
+    assert_eq!(transfers(), vec![(account("artist"), NEAR), (seller, NEAR)]);
+  }
+}
@@ -0,0 +1,27 @@
+// Upgrade price
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_upgrade_price {
+    ($contract: ident, $tokens: ident) => {
+        use $crate::{NonFungibleTokenUpgradePrice, UpgradePrice};
+
+        #[near_bindgen]
+        impl NonFungibleTokenUpgradePrice for $contract {
+          fn nft_set_upgrade_price(&mut self, ft_token_id: AccountId, token_type: TokenType, rarity: TokenRarity, price: U128) -> UpgradePrice {
+            self.$tokens.nft_set_upgrade_price(ft_token_id, token_type, rarity, price)
+          }
+
+          fn nft_remove_upgrade_price(&mut self, ft_token_id: AccountId, token_type: TokenType, rarity: TokenRarity) {
+            self.$tokens.nft_remove_upgrade_price(ft_token_id, token_type, rarity)
+          }
+
+          fn nft_upgrade_prices(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<UpgradePrice> {
+            self.$tokens.nft_upgrade_prices(from_index, limit)
+          }
+
+          fn nft_upgrade_price(&self, token_id: TokenId, ft_token_id: AccountId) -> U128 {
+            self.$tokens.nft_upgrade_price(token_id, ft_token_id)
+          }
+        }
+    };
+}
@@ -0,0 +1,13 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::json_types::U128;
+use near_sdk::AccountId;
+use crate::nft::{TokenRarity, TokenType};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradePrice {
+  pub ft_token_id: AccountId,
+  pub token_type: TokenType,
+  pub rarity: TokenRarity,
+  pub price: U128,
+}
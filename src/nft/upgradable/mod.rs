@@ -1,9 +1,24 @@
 pub use upgradable_impl::*;
+pub use metadata::*;
 use near_sdk::AccountId;
-use crate::nft::TokenId;
+use near_sdk::json_types::U128;
+use crate::nft::{TokenId, TokenRarity, TokenType};
 
+pub mod metadata;
 pub mod upgradable_impl;
+mod macros;
 
 pub trait NonFungibleTokenUpgradable {
   fn nft_upgrade(&mut self, token_id: TokenId);
 }
+
+pub trait NonFungibleTokenUpgradePrice {
+  fn nft_set_upgrade_price(&mut self, ft_token_id: AccountId, token_type: TokenType, rarity: TokenRarity, price: U128) -> UpgradePrice;
+
+  fn nft_remove_upgrade_price(&mut self, ft_token_id: AccountId, token_type: TokenType, rarity: TokenRarity);
+
+  fn nft_upgrade_prices(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<UpgradePrice>;
+
+  /// Price of upgrading `token_id` to the next rarity, paid in `ft_token_id`
+  fn nft_upgrade_price(&self, token_id: TokenId, ft_token_id: AccountId) -> U128;
+}
@@ -1,7 +1,8 @@
 use crate::nft::{NonFungibleToken, TokenId, TokenRarity, TokenType};
-use crate::nft::upgradable::NonFungibleTokenUpgradable;
-use crate::nft::events_171_mf::NftUpgrade;
-use near_sdk::{AccountId, env};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::upgradable::{NonFungibleTokenUpgradable, NonFungibleTokenUpgradePrice, UpgradePrice};
+use crate::nft::events_171_mf::{NftUpgrade, UpgradePriceUpdate};
+use near_sdk::{AccountId, env, require};
 use near_sdk::json_types::U128;
 
 impl NonFungibleToken {
@@ -29,91 +30,34 @@ impl NonFungibleToken {
     next
   }
 
-  pub(crate) fn internal_upgrade_price(&self, token_type: &TokenType, rarity: &TokenRarity) -> U128 {
-    let price = match token_type {
-      TokenType::Armor => {
-         match rarity {
-          TokenRarity::Uncommon => U128::from(2000000000000000000000000),
-          TokenRarity::Rare => U128::from(7000000000000000000000000),
-          TokenRarity::Uniq => U128::from(24000000000000000000000000),
-          TokenRarity::Epic => U128::from(81000000000000000000000000),
-          TokenRarity::Legendary => U128::from(273000000000000000000000000),
-          TokenRarity::Artefact => U128::from(921000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      TokenType::Weapon => {
-        match rarity {
-          TokenRarity::Uncommon => U128::from(4000000000000000000000000),
-          TokenRarity::Rare => U128::from(12000000000000000000000000),
-          TokenRarity::Uniq => U128::from(40000000000000000000000000),
-          TokenRarity::Epic => U128::from(135000000000000000000000000),
-          TokenRarity::Legendary => U128::from(455000000000000000000000000),
-          TokenRarity::Artefact => U128::from(1535000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      TokenType::Shield => {
-        match rarity {
-          TokenRarity::Uncommon => U128::from(4000000000000000000000000),
-          TokenRarity::Rare => U128::from(12000000000000000000000000),
-          TokenRarity::Uniq => U128::from(40000000000000000000000000),
-          TokenRarity::Epic => U128::from(135000000000000000000000000),
-          TokenRarity::Legendary => U128::from(455000000000000000000000000),
-          TokenRarity::Artefact => U128::from(1535000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      TokenType::Pet => {
-        match rarity {
-          TokenRarity::Uncommon => U128::from(3000000000000000000000000),
-          TokenRarity::Rare => U128::from(11000000000000000000000000),
-          TokenRarity::Uniq => U128::from(36000000000000000000000000),
-          TokenRarity::Epic => U128::from(121000000000000000000000000),
-          TokenRarity::Legendary => U128::from(409000000000000000000000000),
-          TokenRarity::Artefact => U128::from(1381000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      TokenType::Jewelry => {
-        match rarity {
-          TokenRarity::Uncommon => U128::from(2000000000000000000000000),
-          TokenRarity::Rare => U128::from(7000000000000000000000000),
-          TokenRarity::Uniq => U128::from(24000000000000000000000000),
-          TokenRarity::Epic => U128::from(81000000000000000000000000),
-          TokenRarity::Legendary => U128::from(273000000000000000000000000),
-          TokenRarity::Artefact => U128::from(921000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      TokenType::Class => {
-        match rarity {
-          TokenRarity::Uncommon => U128::from(4000000000000000000000000),
-          TokenRarity::Rare => U128::from(14000000000000000000000000),
-          TokenRarity::Uniq => U128::from(48000000000000000000000000),
-          TokenRarity::Epic => U128::from(162000000000000000000000000),
-          TokenRarity::Legendary => U128::from(546000000000000000000000000),
-          TokenRarity::Artefact => U128::from(1842000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      TokenType::Race => {
-        match rarity {
-          TokenRarity::Uncommon => U128::from(2000000000000000000000000),
-          TokenRarity::Rare => U128::from(8000000000000000000000000),
-          TokenRarity::Uniq => U128::from(28000000000000000000000000),
-          TokenRarity::Epic => U128::from(94000000000000000000000000),
-          TokenRarity::Legendary => U128::from(318000000000000000000000000),
-          TokenRarity::Artefact => U128::from(1074000000000000000000000000),
-          _ => unimplemented!(),
-        }
-      },
-      _ => {
-        unimplemented!();
-      }
-    };
+  pub(crate) fn internal_upgrade_price(&self, ft_token_id: &AccountId, token_type: &TokenType, rarity: &TokenRarity) -> U128 {
+    let price = self.upgrade_prices
+      .get(&(ft_token_id.clone(), token_type.clone(), rarity.clone()))
+      .unwrap_or_else(|| env::panic_str(&format!("Upgrade price for {:?} {:?} in {} is not set", token_type, rarity, ft_token_id)));
 
-    price
+    U128::from(price)
+  }
+
+  pub(crate) fn internal_set_upgrade_price(&mut self, ft_token_id: &AccountId, token_type: &TokenType, rarity: &TokenRarity, price: &U128) {
+    self.upgrade_prices.insert(&(ft_token_id.clone(), token_type.clone(), rarity.clone()), &price.0);
+
+    UpgradePriceUpdate {
+      ft_token_id,
+      token_type,
+      rarity,
+      price: Some(price),
+    }.emit();
+  }
+
+  pub(crate) fn internal_remove_upgrade_price(&mut self, ft_token_id: &AccountId, token_type: &TokenType, rarity: &TokenRarity) {
+    self.upgrade_prices.remove(&(ft_token_id.clone(), token_type.clone(), rarity.clone())).expect("Not found upgrade price");
+
+    UpgradePriceUpdate {
+      ft_token_id,
+      token_type,
+      rarity,
+      price: None,
+    }.emit();
   }
 
   pub(crate) fn internal_upgrade_token_unguarded(&mut self, owner_id: &AccountId, token_id: &TokenId, price: &U128, rarity: &TokenRarity) {
@@ -139,3 +83,55 @@ impl NonFungibleTokenUpgradable for NonFungibleToken {
     // self.internal_upgrade_token(&token_id, &U128::from(0));
   }
 }
+
+impl NonFungibleTokenUpgradePrice for NonFungibleToken {
+  fn nft_set_upgrade_price(&mut self, ft_token_id: AccountId, token_type: TokenType, rarity: TokenRarity, price: U128) -> UpgradePrice {
+    self.assert_owner();
+    self.ft_whitelist.assert_whitelisted(&FtPurpose::Upgrade, &ft_token_id);
+
+    self.internal_set_upgrade_price(&ft_token_id, &token_type, &rarity, &price);
+
+    UpgradePrice {
+      ft_token_id,
+      token_type,
+      rarity,
+      price,
+    }
+  }
+
+  fn nft_remove_upgrade_price(&mut self, ft_token_id: AccountId, token_type: TokenType, rarity: TokenRarity) {
+    self.assert_owner();
+
+    self.internal_remove_upgrade_price(&ft_token_id, &token_type, &rarity);
+  }
+
+  fn nft_upgrade_prices(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<UpgradePrice> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.upgrade_prices.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.upgrade_prices
+      .iter()
+      .skip(start_index as usize)
+      .take(limit)
+      .map(|((ft_token_id, token_type, rarity), price)| UpgradePrice {
+        ft_token_id,
+        token_type,
+        rarity,
+        price: U128::from(price),
+      })
+      .collect()
+  }
+
+  fn nft_upgrade_price(&self, token_id: TokenId, ft_token_id: AccountId) -> U128 {
+    let next_rarity = self.assert_next_rarity(&token_id);
+    let token_type = self.token_type_by_id.as_ref().unwrap().get(&token_id).expect("Not found token");
+
+    self.internal_upgrade_price(&ft_token_id, &token_type, &next_rarity)
+  }
+}
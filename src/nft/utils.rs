@@ -1,10 +1,48 @@
-use near_sdk::{env, require, AccountId, Balance, CryptoHash, Promise};
+use near_sdk::{env, ext_contract, require, AccountId, Balance, CryptoHash, Gas, Promise, StorageUsage};
+use near_sdk::json_types::U128;
 use std::collections::HashMap;
 use std::mem::size_of;
 use rand::{SeedableRng, StdRng};
 use std::convert::TryInto;
 
-// TODO: need a way for end users to determine how much an approval will cost.
+/// Check inclusion of `leaf` in a merkle tree built with sorted pairs of sha256 hashes
+pub fn verify_merkle_proof(root: &CryptoHash, leaf: CryptoHash, proof: &[CryptoHash]) -> bool {
+  let hash = proof.iter().fold(leaf, |hash, node| {
+    let pair = if hash <= *node {
+      [hash, *node].concat()
+    } else {
+      [*node, hash].concat()
+    };
+
+    env::sha256(&pair).try_into().unwrap()
+  });
+
+  &hash == root
+}
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+const ONE_YOCTO: Balance = 1;
+
+#[ext_contract(ext_ft)]
+pub trait FungibleTokenCore {
+  fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+/// Send NEAR, or the fungible token `ft_token` when it is set
+pub fn transfer_near_or_ft(ft_token: &Option<AccountId>, receiver_id: &AccountId, amount: Balance, memo: String) -> Promise {
+  match ft_token {
+    Some(ft_token) => ext_ft::ft_transfer(
+      receiver_id.clone(),
+      U128::from(amount),
+      Some(memo),
+      ft_token.clone(),
+      ONE_YOCTO,
+      GAS_FOR_FT_TRANSFER,
+    ),
+    None => Promise::new(receiver_id.clone()).transfer(amount),
+  }
+}
+
 pub fn bytes_for_approved_account_id(account_id: &AccountId) -> u64 {
   // The extra 4 bytes are coming from Borsh serialization to store the length of the string.
   account_id.as_str().len() as u64 + 4 + size_of::<u64>() as u64
@@ -28,25 +66,39 @@ pub fn refund_approved_account_ids(
   refund_approved_account_ids_iter(account_id, approved_account_ids.keys())
 }
 
-// pub fn refund_deposit_to_account(storage_used: u64, account_id: AccountId) {
-//   let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
-//   let attached_deposit = env::attached_deposit();
-//
-//   require!(
-//         required_cost <= attached_deposit,
-//         format!("Must attach {} yoctoNEAR to cover storage", required_cost)
-//     );
-//
-//   let refund = attached_deposit - required_cost;
-//   if refund > 1 {
-//     Promise::new(account_id).transfer(refund);
-//   }
-// }
+pub fn refund_deposit_to_account(storage_used: u64, account_id: AccountId) {
+  refund_deposit_to_account_with_price(storage_used, 0, account_id)
+}
 
 /// Assumes that the precedecessor will be refunded
-// pub fn refund_deposit(storage_used: u64) {
-//   refund_deposit_to_account(storage_used, env::predecessor_account_id())
-// }
+pub fn refund_deposit(storage_used: u64) {
+  refund_deposit_to_account(storage_used, env::predecessor_account_id())
+}
+
+/// Charge `price` plus the storage used from the attached deposit and refund the rest
+pub fn refund_deposit_to_account_with_price(storage_used: u64, price: Balance, account_id: AccountId) {
+  let required_cost = env::storage_byte_cost() * Balance::from(storage_used) + price;
+  let attached_deposit = env::attached_deposit();
+
+  require!(
+        required_cost <= attached_deposit,
+        format!("Must attach {} yoctoNEAR to cover price and storage", required_cost)
+    );
+
+  let refund = attached_deposit - required_cost;
+  if refund > 1 {
+    Promise::new(account_id).transfer(refund);
+  }
+}
+
+/// Storage added since `initial_storage_usage`, released storage counts as zero
+pub fn storage_used_since(initial_storage_usage: StorageUsage) -> StorageUsage {
+  env::storage_usage().saturating_sub(initial_storage_usage)
+}
+
+pub fn storage_cost(storage: StorageUsage) -> Balance {
+  env::storage_byte_cost() * Balance::from(storage)
+}
 
 pub fn hash_account_id(account_id: &AccountId) -> CryptoHash {
   let mut hash = CryptoHash::default();
@@ -0,0 +1,141 @@
+use near_sdk::{AccountId, env, Balance};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId};
+use crate::nft::vault::{Vault, VaultBuyout};
+use crate::nft::events_171_mf::{VaultTransfer, VaultBuyout as VaultBuyoutEvent, VaultRedeem};
+
+impl NonFungibleToken {
+  pub(crate) fn assert_token_not_in_vault(&self, token_id: &TokenId) {
+    if let Some(vault) = self.vault_by_token.get(token_id) {
+      if vault.buyout.is_none() {
+        env::panic_str("Token is locked in vault");
+      }
+    }
+  }
+
+  pub(crate) fn internal_vault_balance(&self, token_id: &TokenId, account_id: &AccountId) -> Balance {
+    self.vault_shares.get(&(token_id.clone(), account_id.clone())).unwrap_or(0)
+  }
+
+  /// Zero balances are removed to release their storage
+  pub(crate) fn internal_vault_set_balance(&mut self, token_id: &TokenId, account_id: &AccountId, balance: Balance) {
+    let key = (token_id.clone(), account_id.clone());
+
+    if balance == 0 {
+      self.vault_shares.remove(&key);
+    } else {
+      self.vault_shares.insert(&key, &balance);
+    }
+  }
+
+  pub(crate) fn internal_vault_transfer(&mut self, token_id: &TokenId, sender_id: &AccountId, receiver_id: &AccountId, amount: Balance, memo: Option<String>) {
+    assert!(self.vault_by_token.get(token_id).is_some(), "Not found vault");
+    assert_ne!(sender_id, receiver_id, "Sender and receiver should be different");
+    assert!(amount > 0, "The amount should be a positive number");
+
+    let sender_balance = self.internal_vault_balance(token_id, sender_id);
+    assert!(sender_balance >= amount, "The account doesn't have enough balance");
+
+    let receiver_balance = self.internal_vault_balance(token_id, receiver_id);
+
+    self.internal_vault_set_balance(token_id, sender_id, sender_balance - amount);
+    self.internal_vault_set_balance(token_id, receiver_id, receiver_balance.checked_add(amount).expect("Balance overflow"));
+
+    VaultTransfer {
+      token_id,
+      old_owner_id: sender_id,
+      new_owner_id: receiver_id,
+      amount: &U128::from(amount),
+      memo: memo.as_deref(),
+    }.emit();
+  }
+
+  /// Hand the token over to `receiver_id` for `amount`, royalties are paid at once and the rest is kept
+  /// for the shareholders
+  pub(crate) fn internal_vault_buyout(&mut self, token_id: &TokenId, buyer_id: &AccountId, receiver_id: &AccountId, amount: Balance, ft_token: Option<AccountId>) -> Vault {
+    let mut vault = self.vault_by_token.get(token_id).expect("Not found vault");
+
+    assert!(vault.buyout.is_none(), "Vault is already bought out");
+    assert_eq!(vault.ft_token, ft_token, "Invalid vault currency");
+    assert!(amount >= vault.reserve_price.0, "Invalid attached price");
+
+    let contract_id = env::current_account_id();
+    let price = vault.reserve_price.0;
+
+    let mut payout = self.internal_payout(token_id, &contract_id, price, None);
+    let proceeds = payout.remove(&contract_id).map(|amount| amount.0).unwrap_or(0);
+
+    payout
+      .iter()
+      .filter(|(_, amount)| amount.0 > 0)
+      .for_each(|(account_id, amount)| {
+        self.internal_payout_transfer(&vault.ft_token, account_id, amount.0, format!("Vault {} buyout", token_id));
+      });
+
+    vault.buyout = Some(VaultBuyout {
+      buyer_id: buyer_id.clone(),
+      price: vault.reserve_price,
+      proceeds: U128::from(proceeds),
+      shares: vault.total_supply,
+      bought_at: env::block_timestamp(),
+    });
+    self.vault_by_token.insert(token_id, &vault);
+
+    self.internal_transfer_unguarded(token_id, &contract_id, receiver_id);
+
+    VaultBuyoutEvent {
+      token_id,
+      buyer_id,
+      receiver_id,
+      ft_token: vault.ft_token.as_ref(),
+      price: &vault.reserve_price,
+    }.emit();
+
+    vault
+  }
+
+  /// The last shares take whatever is left, so rounding never locks proceeds in the contract
+  pub(crate) fn internal_vault_redeem(&mut self, token_id: &TokenId, account_id: &AccountId) -> Balance {
+    let mut vault = self.vault_by_token.get(token_id).expect("Not found vault");
+    let mut buyout = vault.buyout.clone().expect("Vault is not bought out");
+
+    let shares = self.internal_vault_balance(token_id, account_id);
+    assert!(shares > 0, "The account doesn't have shares");
+
+    // supply fits u64, so the remainder part cannot overflow
+    let amount = if shares == buyout.shares.0 {
+      buyout.proceeds.0
+    } else {
+      let quotient = buyout.proceeds.0 / buyout.shares.0;
+      let remainder = buyout.proceeds.0 % buyout.shares.0;
+
+      quotient * shares + remainder * shares / buyout.shares.0
+    };
+
+    buyout.proceeds = U128::from(buyout.proceeds.0 - amount);
+    buyout.shares = U128::from(buyout.shares.0 - shares);
+    let redeemed = buyout.shares.0 == 0;
+    vault.buyout = Some(buyout);
+    self.internal_vault_set_balance(token_id, account_id, 0);
+
+    // the token can be put in a vault again once every share is redeemed
+    if redeemed {
+      self.vault_by_token.remove(token_id);
+    } else {
+      self.vault_by_token.insert(token_id, &vault);
+    }
+
+    if amount > 0 {
+      self.internal_payout_transfer(&vault.ft_token, account_id, amount, format!("Vault {} redeem", token_id));
+    }
+
+    VaultRedeem {
+      token_id,
+      account_id,
+      shares: &U128::from(shares),
+      amount: &U128::from(amount),
+    }.emit();
+
+    amount
+  }
+}
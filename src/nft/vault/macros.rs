@@ -0,0 +1,58 @@
+// Vault
+
+#[macro_export]
+macro_rules! impl_non_fungible_token_vault {
+    ($contract: ident, $tokens: ident $(, $assert_transfer: ident)?) => {
+        use $crate::nft::vault::{NonFungibleTokenVault, NonFungibleTokenVaultEnumeration, NonFungibleTokenVaultShares, Vault, FungibleTokenMetadata};
+
+        #[near_bindgen]
+        impl NonFungibleTokenVault for $contract {
+          #[payable]
+          fn nft_vault_create(&mut self, token_id: TokenId, total_supply: U128, ft_token: Option<AccountId>, reserve_price: U128) -> Vault {
+            $(self.$assert_transfer(&token_id, &env::current_account_id());)?
+
+            self.$tokens.nft_vault_create(token_id, total_supply, ft_token, reserve_price)
+          }
+
+          #[payable]
+          fn nft_vault_buyout(&mut self, token_id: TokenId, receiver_id: Option<AccountId>) -> Vault {
+            self.$tokens.nft_vault_buyout(token_id, receiver_id)
+          }
+
+          fn nft_vault_redeem(&mut self, token_id: TokenId) -> U128 {
+            self.$tokens.nft_vault_redeem(token_id)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenVaultEnumeration for $contract {
+          fn nft_vault(&self, token_id: TokenId) -> Option<Vault> {
+            self.$tokens.nft_vault(token_id)
+          }
+
+          fn nft_vaults(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Vault> {
+            self.$tokens.nft_vaults(from_index, limit)
+          }
+        }
+
+        #[near_bindgen]
+        impl NonFungibleTokenVaultShares for $contract {
+          #[payable]
+          fn ft_transfer(&mut self, token_id: TokenId, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+            self.$tokens.ft_transfer(token_id, receiver_id, amount, memo)
+          }
+
+          fn ft_total_supply(&self, token_id: TokenId) -> U128 {
+            self.$tokens.ft_total_supply(token_id)
+          }
+
+          fn ft_balance_of(&self, token_id: TokenId, account_id: AccountId) -> U128 {
+            self.$tokens.ft_balance_of(token_id, account_id)
+          }
+
+          fn ft_metadata(&self, token_id: TokenId) -> FungibleTokenMetadata {
+            self.$tokens.ft_metadata(token_id)
+          }
+        }
+    };
+}
@@ -0,0 +1,45 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::AccountId;
+use crate::nft::TokenId;
+
+/// Token locked in the contract and split into `total_supply` shares, the buyout is paid in `ft_token`
+/// or in NEAR when it is `None`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Vault {
+  pub token_id: TokenId,
+  pub curator_id: AccountId,
+  pub total_supply: U128,
+  pub ft_token: Option<AccountId>,
+  pub reserve_price: U128,
+  pub created_at: u64,
+  pub buyout: Option<VaultBuyout>,
+}
+
+/// `proceeds` are left for the `shares` that are not redeemed yet
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VaultBuyout {
+  pub buyer_id: AccountId,
+  pub price: U128,
+  pub proceeds: U128,
+  pub shares: U128,
+  pub bought_at: u64,
+}
+
+pub const FT_METADATA_SPEC: &str = "ft-1.0.0";
+
+/// NEP-148 metadata of vault shares, shares are not divisible
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FungibleTokenMetadata {
+  pub spec: String,
+  pub name: String,
+  pub symbol: String,
+  pub icon: Option<String>,
+  pub reference: Option<String>,
+  pub reference_hash: Option<Base64VecU8>,
+  pub decimals: u8,
+}
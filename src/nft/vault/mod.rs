@@ -0,0 +1,42 @@
+pub use metadata::*;
+use near_sdk::AccountId;
+use near_sdk::json_types::U128;
+use crate::nft::TokenId;
+
+pub mod metadata;
+pub mod vault_impl;
+mod internal;
+mod macros;
+
+/// Fractional ownership of a token locked in the contract, shares are balances kept per vault.
+/// Shares of a vault follow NEP-141 through [`NonFungibleTokenVaultShares`]. A buyout for the reserve price hands the token over to the buyer, shareholders redeem the
+/// proceeds pro rata and the vault is removed once all shares are redeemed.
+pub trait NonFungibleTokenVault {
+  /// Lock the token of the caller, all shares go to the caller
+  fn nft_vault_create(&mut self, token_id: TokenId, total_supply: U128, ft_token: Option<AccountId>, reserve_price: U128) -> Vault;
+
+  /// Buy out a vault in NEAR, the rest of the attached deposit is refunded
+  fn nft_vault_buyout(&mut self, token_id: TokenId, receiver_id: Option<AccountId>) -> Vault;
+
+  /// Burn all shares of the caller for their part of the buyout proceeds
+  fn nft_vault_redeem(&mut self, token_id: TokenId) -> U128;
+}
+
+pub trait NonFungibleTokenVaultEnumeration {
+  fn nft_vault(&self, token_id: TokenId) -> Option<Vault>;
+
+  fn nft_vaults(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Vault>;
+}
+
+/// NEP-141 interface of the shares, the vault is selected by the locked `token_id`
+pub trait NonFungibleTokenVaultShares {
+  /// Requires 1 yoctoNEAR, storage of a new holder is paid from the storage balance of the sender
+  fn ft_transfer(&mut self, token_id: TokenId, receiver_id: AccountId, amount: U128, memo: Option<String>);
+
+  fn ft_total_supply(&self, token_id: TokenId) -> U128;
+
+  fn ft_balance_of(&self, token_id: TokenId, account_id: AccountId) -> U128;
+
+  /// NEP-148 metadata of the shares, taken from the metadata of the locked token
+  fn ft_metadata(&self, token_id: TokenId) -> FungibleTokenMetadata;
+}
@@ -0,0 +1,236 @@
+use near_sdk::{assert_one_yocto, env, require, AccountId};
+use near_sdk::json_types::U128;
+use crate::nft::{NonFungibleToken, TokenId, refund_approved_account_ids, storage_used_since};
+use crate::nft::vault::{NonFungibleTokenVault, NonFungibleTokenVaultEnumeration, NonFungibleTokenVaultShares, Vault, FungibleTokenMetadata, FT_METADATA_SPEC};
+use crate::nft::ft_whitelist::FtPurpose;
+use crate::nft::events_171_mf::VaultCreate;
+
+impl NonFungibleTokenVault for NonFungibleToken {
+  fn nft_vault_create(&mut self, token_id: TokenId, total_supply: U128, ft_token: Option<AccountId>, reserve_price: U128) -> Vault {
+    let owner_id = self.assert_token_holder(&token_id);
+    let initial_storage_usage = env::storage_usage();
+
+    if let Some(vault) = self.vault_by_token.get(&token_id) {
+      assert!(vault.buyout.is_none(), "Vault shares are not redeemed yet");
+      env::panic_str("Vault already exists");
+    }
+    assert!(total_supply.0 > 0 && total_supply.0 <= u64::MAX as u128, "Invalid total supply");
+    assert!(reserve_price.0 > 0, "Reserve price must be greater than 0");
+
+    if let Some(ft_token) = &ft_token {
+      self.ft_whitelist.assert_whitelisted(&FtPurpose::Vault, ft_token);
+    }
+
+    // the contract holds the token until the buyout
+    let (_, approved_account_ids) = self.internal_transfer(&owner_id, &env::current_account_id(), &token_id, None, None);
+
+    if let Some(approved_account_ids) = approved_account_ids {
+      refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+    }
+
+    let vault = Vault {
+      token_id: token_id.clone(),
+      curator_id: owner_id.clone(),
+      total_supply,
+      ft_token,
+      reserve_price,
+      created_at: env::block_timestamp(),
+      buyout: None,
+    };
+
+    self.vault_by_token.insert(&token_id, &vault);
+    self.internal_vault_set_balance(&token_id, &owner_id, total_supply.0);
+
+    VaultCreate {
+      vault: &vault,
+    }.emit();
+
+    self.internal_storage_charge(&owner_id, storage_used_since(initial_storage_usage), 0);
+
+    vault
+  }
+
+  fn nft_vault_buyout(&mut self, token_id: TokenId, receiver_id: Option<AccountId>) -> Vault {
+    let buyer_id = env::predecessor_account_id();
+    let receiver_id = receiver_id.unwrap_or_else(|| buyer_id.clone());
+    let initial_storage_usage = env::storage_usage();
+
+    let vault = self.internal_vault_buyout(&token_id, &buyer_id, &receiver_id, env::attached_deposit(), None);
+
+    self.internal_storage_charge(&buyer_id, storage_used_since(initial_storage_usage), vault.reserve_price.0);
+
+    vault
+  }
+
+  fn nft_vault_redeem(&mut self, token_id: TokenId) -> U128 {
+    let account_id = env::predecessor_account_id();
+
+    U128::from(self.internal_vault_redeem(&token_id, &account_id))
+  }
+}
+
+impl NonFungibleTokenVaultEnumeration for NonFungibleToken {
+  fn nft_vault(&self, token_id: TokenId) -> Option<Vault> {
+    self.vault_by_token.get(&token_id)
+  }
+
+  fn nft_vaults(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Vault> {
+    let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+
+    if (self.vault_by_token.len() as u128) <= start_index {
+      return vec![];
+    }
+
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    require!(limit != 0, "Cannot provide limit of 0.");
+
+    self.vault_by_token
+      .values()
+      .skip(start_index as usize)
+      .take(limit)
+      .collect()
+  }
+}
+
+impl NonFungibleTokenVaultShares for NonFungibleToken {
+  fn ft_transfer(&mut self, token_id: TokenId, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+    assert_one_yocto();
+
+    let sender_id = env::predecessor_account_id();
+    let initial_storage_usage = env::storage_usage();
+
+    self.internal_vault_transfer(&token_id, &sender_id, &receiver_id, amount.0, memo);
+
+    let storage_used = storage_used_since(initial_storage_usage);
+    if storage_used > 0 {
+      assert!(self.internal_storage_use(&sender_id, storage_used), "Storage deposit is required for a new holder");
+    }
+  }
+
+  fn ft_total_supply(&self, token_id: TokenId) -> U128 {
+    self.vault_by_token.get(&token_id).map(|vault| vault.total_supply).unwrap_or(U128::from(0))
+  }
+
+  fn ft_balance_of(&self, token_id: TokenId, account_id: AccountId) -> U128 {
+    U128::from(self.internal_vault_balance(&token_id, &account_id))
+  }
+
+  fn ft_metadata(&self, token_id: TokenId) -> FungibleTokenMetadata {
+    assert!(self.vault_by_token.get(&token_id).is_some(), "Not found vault");
+
+    let metadata = self.token_metadata_by_id.as_ref().and_then(|by_id| by_id.get(&token_id));
+
+    FungibleTokenMetadata {
+      spec: FT_METADATA_SPEC.to_string(),
+      name: metadata.as_ref().and_then(|metadata| metadata.title.clone()).unwrap_or_else(|| token_id.clone()),
+      symbol: token_id,
+      icon: None,
+      reference: metadata.as_ref().and_then(|metadata| metadata.reference.clone()),
+      reference_hash: metadata.and_then(|metadata| metadata.reference_hash),
+      decimals: 0,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::nft::StorageManagement;
+  use crate::nft::test_utils::*;
+
+  /// Vault of 3 shares split between the curator, bob and carol, bought out for 10 yoctoNEAR
+  fn bought_out_vault(tokens: &mut NonFungibleToken, curator_id: &AccountId) -> TokenId {
+    let token_id = "token".to_string();
+    mint(tokens, &token_id, Some(curator_id), None);
+
+    set_context(&context(curator_id, NEAR));
+    tokens.storage_deposit(None, None);
+    tokens.nft_vault_create(token_id.clone(), U128::from(3), None, U128::from(10));
+
+    set_context(&context(curator_id, 1));
+    tokens.ft_transfer(token_id.clone(), account("bob"), U128::from(1), None);
+    tokens.ft_transfer(token_id.clone(), account("carol"), U128::from(1), None);
+
+    set_context(&context(&account("buyer"), NEAR));
+    tokens.nft_vault_buyout(token_id.clone(), None);
+
+    token_id
+  }
+
+  fn redeem(tokens: &mut NonFungibleToken, token_id: &TokenId, account_id: &AccountId) -> u128 {
+    set_context(&context(account_id, 0));
+
+    tokens.nft_vault_redeem(token_id.clone()).0
+  }
+
+  #[test]
+  fn buyout_hands_over_the_token() {
+    let mut tokens = new_tokens();
+    let token_id = bought_out_vault(&mut tokens, &account("curator"));
+
+    assert_eq!(tokens.owner_by_id.get(&token_id), Some(account("buyer")));
+    assert_eq!(tokens.nft_vault(token_id).unwrap().buyout.unwrap().proceeds.0, 10);
+  }
+
+  #[test]
+  fn last_shares_take_the_remainder_and_remove_the_vault() {
+    let mut tokens = new_tokens();
+    let curator = account("curator");
+    let token_id = bought_out_vault(&mut tokens, &curator);
+
+    assert_eq!(redeem(&mut tokens, &token_id, &account("bob")), 3);
+    assert_eq!(transfers(), vec![(account("bob"), 3)]);
+    assert_eq!(redeem(&mut tokens, &token_id, &curator), 3);
+    assert!(tokens.nft_vault(token_id.clone()).is_some());
+    assert_eq!(redeem(&mut tokens, &token_id, &account("carol")), 4);
+
+    assert!(tokens.nft_vault(token_id.clone()).is_none());
+    assert_eq!(tokens.ft_balance_of(token_id, curator).0, 0);
+  }
+
+  #[test]
+  fn shares_follow_nep141() {
+    let mut tokens = new_tokens();
+    let curator = account("curator");
+    mint(&mut tokens, "token", Some(&curator), None);
+
+    set_context(&context(&curator, NEAR));
+    tokens.storage_deposit(None, None);
+    tokens.nft_vault_create("token".to_string(), U128::from(3), None, U128::from(10));
+
+    set_context(&context(&curator, 1));
+    tokens.ft_transfer("token".to_string(), account("bob"), U128::from(1), None);
+
+    assert_eq!(tokens.ft_total_supply("token".to_string()).0, 3);
+    assert_eq!(tokens.ft_balance_of("token".to_string(), curator).0, 2);
+    assert_eq!(tokens.ft_balance_of("token".to_string(), account("bob")).0, 1);
+
+    let metadata = tokens.ft_metadata("token".to_string());
+    assert_eq!(metadata.spec, FT_METADATA_SPEC);
+    assert_eq!(metadata.symbol, "token");
+    assert_eq!(metadata.decimals, 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "The account doesn't have shares")]
+  fn redeem_twice_fails() {
+    let mut tokens = new_tokens();
+    let token_id = bought_out_vault(&mut tokens, &account("curator"));
+
+    redeem(&mut tokens, &token_id, &account("bob"));
+    redeem(&mut tokens, &token_id, &account("bob"));
+  }
+
+  #[test]
+  #[should_panic(expected = "Vault is not bought out")]
+  fn redeem_before_buyout_fails() {
+    let mut tokens = new_tokens();
+    let curator = account("curator");
+    mint(&mut tokens, "token", Some(&curator), None);
+
+    set_context(&context(&curator, NEAR));
+    tokens.nft_vault_create("token".to_string(), U128::from(3), None, U128::from(10));
+
+    redeem(&mut tokens, &"token".to_string(), &curator);
+  }
+}